@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use gtk::glib;
+
+/// The audio level of the microphone, as measured by the `level` element.
+///
+/// See the [`Viewfinder::audio-level`][crate::Viewfinder#audio-level] signal.
+#[derive(Debug, Clone, PartialEq, glib::Boxed)]
+#[boxed_type(name = "ApertureAudioLevel")]
+pub struct AudioLevel {
+    peak: Vec<f64>,
+    rms: Vec<f64>,
+}
+
+impl AudioLevel {
+    /// Gets the peak level of each channel, in dB.
+    pub fn peak(&self) -> &[f64] {
+        &self.peak
+    }
+
+    /// Gets the RMS level of each channel, in dB.
+    pub fn rms(&self) -> &[f64] {
+        &self.rms
+    }
+
+    /// Gets the highest peak level among the channels, in dB.
+    ///
+    /// # Returns
+    ///
+    /// the peak level, or `None` if there are no channels.
+    pub fn max_peak(&self) -> Option<f64> {
+        self.peak.iter().copied().reduce(f64::max)
+    }
+
+    /// Parses the `level` message posted by the `level` element.
+    pub(crate) fn from_structure(structure: &gst::StructureRef) -> Option<Self> {
+        let channels = |field: &str| {
+            structure.get::<glib::ValueArray>(field).ok().map(|values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.get::<f64>().ok())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        Some(Self {
+            peak: channels("peak")?,
+            rms: channels("rms")?,
+        })
+    }
+}
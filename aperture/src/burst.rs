@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use gst::prelude::*;
+use gtk::{gio, glib};
+
+#[derive(Debug, Default)]
+struct State {
+    taken: u32,
+    // The timestamp from which the next frame is taken.
+    next_pts: Option<gst::ClockTime>,
+}
+
+/// A branch of the viewfinder stream saving a sequence of frames as
+/// pictures, without interrupting the stream. It is also used to take
+/// single pictures while recording.
+#[derive(Debug)]
+pub(crate) struct Burst {
+    bin: gst::Element,
+    sink: gst::Element,
+    count: u32,
+    files: RefCell<Vec<gio::File>>,
+}
+
+impl Burst {
+    /// Creates a branch saving `count` frames encoded with `profile`, taken at
+    /// least `interval` apart. The pictures are named after `stem` followed by
+    /// their number and `extension`.
+    pub fn new(
+        stem: &Path,
+        extension: &str,
+        profile: &gst_pbutils::EncodingProfile,
+        count: u32,
+        interval: Duration,
+    ) -> Result<Self, glib::BoolError> {
+        // The location is a printf pattern.
+        let stem = stem.display().to_string().replace('%', "%%");
+        let extension = extension.replace('%', "%%");
+        let pattern = format!("{stem} %03d.{extension}");
+
+        Self::with_pattern(&pattern, profile, count, interval)
+    }
+
+    /// Creates a branch saving the next frame to `location`, encoded with
+    /// `profile`.
+    pub fn single(
+        location: &Path,
+        profile: &gst_pbutils::EncodingProfile,
+    ) -> Result<Self, glib::BoolError> {
+        let pattern = location.display().to_string().replace('%', "%%");
+        Self::with_pattern(&pattern, profile, 1, Duration::ZERO)
+    }
+
+    fn with_pattern(
+        pattern: &str,
+        profile: &gst_pbutils::EncodingProfile,
+        count: u32,
+        interval: Duration,
+    ) -> Result<Self, glib::BoolError> {
+        let bin = gst::Bin::new();
+
+        let videoconvert = gst::ElementFactory::make("videoconvert").build()?;
+        let encodebin = gst::ElementFactory::make("encodebin")
+            .property("profile", profile)
+            .build()?;
+        let sink = gst::ElementFactory::make("multifilesink")
+            .property("location", pattern)
+            .property("index", 1i32)
+            .property("post-messages", true)
+            .property("async", false)
+            .build()?;
+
+        bin.add_many([&videoconvert, &encodebin, &sink]).unwrap();
+        let encodebin_pad = encodebin
+            .request_pad_simple("video_%u")
+            .ok_or_else(|| glib::bool_error!("Could not get a video pad from encodebin"))?;
+        videoconvert
+            .static_pad("src")
+            .unwrap()
+            .link(&encodebin_pad)
+            .map_err(|err| glib::bool_error!("Could not link to encodebin: {err}"))?;
+        encodebin.link(&sink).unwrap();
+
+        let pad = videoconvert.static_pad("sink").unwrap();
+        let interval = gst::ClockTime::try_from(interval).unwrap_or(gst::ClockTime::ZERO);
+        let state = Mutex::new(State::default());
+        pad.add_probe(gst::PadProbeType::BUFFER, move |_, info| {
+            let Some(gst::PadProbeData::Buffer(ref buffer)) = info.data else {
+                return gst::PadProbeReturn::Ok;
+            };
+
+            let mut state = state.lock().unwrap();
+            let pts = buffer.pts();
+            let is_due = match state.next_pts {
+                Some(next_pts) => pts.is_some_and(|pts| pts >= next_pts),
+                None => true,
+            };
+            if state.taken >= count || !is_due {
+                return gst::PadProbeReturn::Drop;
+            }
+
+            state.taken += 1;
+            state.next_pts = pts.map(|pts| pts + interval);
+
+            gst::PadProbeReturn::Ok
+        });
+
+        let ghost_pad = gst::GhostPad::with_target(&pad).unwrap();
+        ghost_pad.set_active(true).unwrap();
+        bin.add_pad(&ghost_pad).unwrap();
+
+        Ok(Self {
+            bin: bin.upcast(),
+            sink,
+            count,
+            files: RefCell::default(),
+        })
+    }
+
+    pub fn bin(&self) -> &gst::Element {
+        &self.bin
+    }
+
+    /// Handles the message posted by the sink after saving a picture.
+    ///
+    /// # Returns
+    ///
+    /// the number of the picture within the burst, starting at 1, and its
+    /// file, or `None` if the message does not belong to this burst.
+    pub fn add_file(
+        &self,
+        src: Option<&gst::Object>,
+        structure: &gst::StructureRef,
+    ) -> Option<(u32, gio::File)> {
+        if src != Some(self.sink.upcast_ref()) {
+            return None;
+        }
+
+        let path = structure.get::<PathBuf>("filename").ok()?;
+        let file = gio::File::for_path(path);
+        let mut files = self.files.borrow_mut();
+        files.push(file.clone());
+
+        Some((files.len() as u32, file))
+    }
+
+    /// Whether all the pictures of the burst are saved.
+    pub fn is_done(&self) -> bool {
+        self.files.borrow().len() as u32 >= self.count
+    }
+
+    /// The files saved so far.
+    pub fn files(&self) -> gio::ListStore {
+        let store = gio::ListStore::new::<gio::File>();
+        store.extend_from_slice(&self.files.borrow());
+        store
+    }
+}
@@ -6,6 +6,7 @@ use gtk::glib;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
 
+use crate::camera_control::{ControlBackend, MockControls, NoControls};
 use crate::utils;
 
 mod imp {
@@ -20,6 +21,7 @@ mod imp {
     pub struct Camera {
         #[property(get, set, construct_only)]
         device: OnceCell<gst::Device>,
+        pub controls: OnceCell<Box<dyn ControlBackend>>,
     }
 
     #[glib::object_subclass]
@@ -76,6 +78,20 @@ impl Camera {
         })
     }
 
+    /// Gets the manufacturer of the camera represented by `self`.
+    ///
+    /// # Returns
+    ///
+    /// the manufacturer if known.
+    pub fn manufacturer(&self) -> Option<String> {
+        self.device().properties().and_then(|properties| {
+            properties
+                .value("device.vendor.name")
+                .ok()
+                .and_then(|value| value.get::<String>().ok())
+        })
+    }
+
     /// Gets all the available properties for the camera represented by `self`.
     ///
     /// # Returns
@@ -136,6 +152,76 @@ impl Camera {
         }
     }
 
+    /// Gets the modes supported by the camera represented by `self`.
+    ///
+    /// Lists, arrays and ranges in the caps of the camera are expanded, ranges
+    /// of framerates into the common framerates they contain.
+    ///
+    /// # Returns
+    ///
+    /// the supported [`CameraMode`][crate::CameraMode]s, from highest to
+    /// lowest resolution.
+    pub fn modes(&self) -> Vec<crate::CameraMode> {
+        self.caps()
+            .map(|caps| utils::caps::modes(&caps))
+            .unwrap_or_default()
+    }
+
+    /// Whether `mode` can be negotiated with the camera. Modes are compared
+    /// with the caps of the camera by intersection, as they only describe the
+    /// encoding, format, size and framerate while device caps usually have
+    /// more fields, e.g. `pixel-aspect-ratio` or `colorimetry`.
+    pub(crate) fn supports_mode(&self, mode: &crate::CameraMode) -> bool {
+        self.caps()
+            .is_some_and(|caps| mode.caps().can_intersect(&caps))
+    }
+
+    /// Gets the hardware controls of the camera represented by `self`, such
+    /// as its exposure time or focus, with their current values.
+    ///
+    /// Controls are only available for V4L2 devices, including the ones
+    /// provided by Pipewire, when the device can be opened.
+    ///
+    /// # Returns
+    ///
+    /// the supported [`CameraControl`][crate::CameraControl]s.
+    pub fn controls(&self) -> Vec<crate::CameraControl> {
+        self.control_backend().controls()
+    }
+
+    /// Gets the control `id` of the camera represented by `self`.
+    ///
+    /// # Returns
+    ///
+    /// the [`CameraControl`][crate::CameraControl], or `None` if the camera
+    /// does not support it.
+    pub fn control(&self, id: crate::CameraControlId) -> Option<crate::CameraControl> {
+        self.control_backend().control(id)
+    }
+
+    /// Sets the control `id` of the camera represented by `self` to `value`.
+    ///
+    /// Controls can be changed while the camera is streaming. Changing a
+    /// control may make others inactive, e.g. enabling automatic exposure
+    /// makes the exposure time inactive.
+    pub fn set_control(
+        &self,
+        id: crate::CameraControlId,
+        value: i32,
+    ) -> Result<(), crate::ControlError> {
+        let control = self.control(id).ok_or(crate::ControlError::NotSupported)?;
+        control.validate(value)?;
+
+        self.control_backend().set(id, value)
+    }
+
+    fn control_backend(&self) -> &dyn ControlBackend {
+        self.imp()
+            .controls
+            .get_or_init(|| create_control_backend(&self.device()))
+            .as_ref()
+    }
+
     pub(crate) fn new(device: &gst::Device) -> Self {
         glib::Object::builder().property("device", device).build()
     }
@@ -146,7 +232,9 @@ impl Camera {
 
     pub(crate) fn create_element(&self) -> Result<gst::Element, glib::BoolError> {
         let element = self.device().create_element(None)?;
-        element.set_property("client-name", crate::APP_ID.get().unwrap());
+        if element.has_property("client-name", Some(String::static_type())) {
+            element.set_property("client-name", crate::APP_ID.get().unwrap());
+        }
         Ok(element)
     }
 
@@ -163,6 +251,27 @@ impl Camera {
     }
 }
 
+/// Creates the backend reading and changing the controls of `device`.
+fn create_control_backend(device: &gst::Device) -> Box<dyn ControlBackend> {
+    let properties = device.properties();
+    let string = |key: &str| {
+        properties
+            .as_ref()
+            .and_then(|properties| properties.get::<String>(key).ok())
+    };
+
+    if string("device.api").as_deref() == Some("aperture-test") {
+        return Box::new(MockControls::default());
+    }
+
+    let path = string("api.v4l2.path")
+        .or_else(|| string("device.path").filter(|path| path.starts_with("/dev/video")));
+    match path {
+        Some(path) => Box::new(crate::v4l2::V4l2Controls::new(path.into())),
+        None => Box::new(NoControls),
+    }
+}
+
 // For each resolution and format we only keep the highest resolution.
 fn filter_caps(caps: &gst::Caps) -> gst::Caps {
     let mut best_caps = gst::Caps::new_empty();
@@ -178,24 +287,68 @@ fn filter_caps(caps: &gst::Caps) -> gst::Caps {
 }
 
 fn framerate_from_structure(structure: &gst::StructureRef) -> Option<gst::Fraction> {
-    // TODO Handle gst::List and gst::Array
-    if let Ok(framerate) = structure.get::<gst::Fraction>("framerate") {
-        Some(framerate)
-    } else if let Ok(range) = structure.get::<gst::FractionRange>("framerate") {
-        Some(range.max())
-    } else if let Ok(array) = structure.get::<gst::Array>("framerate") {
-        array
-            .iter()
-            .filter_map(|s| s.get::<gst::Fraction>().ok())
-            .filter(|frac| frac <= &gst::Fraction::new(crate::MAXIMUM_RATE, 1))
-            .max()
-    } else if let Ok(array) = structure.get::<gst::List>("framerate") {
-        array
-            .iter()
-            .filter_map(|s| s.get::<gst::Fraction>().ok())
-            .filter(|frac| frac <= &gst::Fraction::new(crate::MAXIMUM_RATE, 1))
-            .max()
-    } else {
-        None
+    utils::caps::fractions(structure, "framerate")
+        .into_iter()
+        .find(|frac| frac <= &gst::Fraction::new(crate::MAXIMUM_RATE, 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use gst::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_supported_modes() {
+        gst::init().expect("Failed to initalize gst");
+
+        // Real devices describe more than the fields of a mode.
+        let caps = [
+            gst_video::VideoCapsBuilder::new()
+                .format(gst_video::VideoFormat::Yuy2)
+                .width(1280)
+                .height(720)
+                .framerate_list([gst::Fraction::new(30, 1), gst::Fraction::new(15, 1)])
+                .pixel_aspect_ratio(gst::Fraction::new(1, 1))
+                .field("colorimetry", "2:4:16:1")
+                .build(),
+            // The maximum resolution of the sensor, used for stills.
+            gst_video::VideoCapsBuilder::for_encoding("image/jpeg")
+                .width(3840)
+                .height(2160)
+                .framerate_range(gst::Fraction::new(5, 1)..=gst::Fraction::new(30, 1))
+                .pixel_aspect_ratio(gst::Fraction::new(1, 1))
+                .field("colorimetry", "1:4:7:1")
+                .build(),
+        ]
+        .into_iter()
+        .collect::<gst::Caps>();
+        let provider = crate::test_provider::TestDeviceProvider::new(0);
+        provider.add_camera("Test Camera", Some(&caps), None);
+        let device = provider
+            .devices()
+            .into_iter()
+            .find(|device| device.device_class() == "Video/Source")
+            .unwrap();
+        let camera = Camera::new(&device);
+
+        let modes = camera.modes();
+        assert_eq!(modes.len(), 2);
+        assert_eq!((modes[0].width(), modes[0].height()), (3840, 2160));
+        for mode in modes {
+            assert!(camera.supports_mode(&mode));
+            for framerate in mode.framerates() {
+                assert!(camera.supports_mode(&mode.with_framerate(*framerate)));
+            }
+        }
+
+        let unsupported = crate::CameraMode::new(
+            "video/x-raw",
+            Some(gst_video::VideoFormat::Yuy2),
+            1920,
+            1080,
+            vec![gst::Fraction::new(30, 1)],
+        );
+        assert!(!camera.supports_mode(&unsupported));
     }
 }
@@ -0,0 +1,272 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use std::cell::RefCell;
+
+use gtk::glib;
+
+use crate::{CameraControlId, CameraControlType, ControlError};
+
+/// A hardware control of a camera, such as its exposure time or focus, as
+/// it was when it was queried.
+///
+/// See [`fn@crate::Camera::controls`].
+#[derive(Debug, Clone, PartialEq, glib::Boxed)]
+#[boxed_type(name = "ApertureCameraControl")]
+pub struct CameraControl {
+    pub(crate) id: CameraControlId,
+    pub(crate) name: String,
+    pub(crate) control_type: CameraControlType,
+    pub(crate) minimum: i32,
+    pub(crate) maximum: i32,
+    pub(crate) step: i32,
+    pub(crate) default_value: i32,
+    pub(crate) value: i32,
+    pub(crate) menu_items: Vec<(i32, String)>,
+    pub(crate) is_read_only: bool,
+    pub(crate) is_inactive: bool,
+}
+
+impl CameraControl {
+    pub fn id(&self) -> CameraControlId {
+        self.id
+    }
+
+    /// Gets the name of the control, as given by the device.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn control_type(&self) -> CameraControlType {
+        self.control_type
+    }
+
+    pub fn minimum(&self) -> i32 {
+        self.minimum
+    }
+
+    pub fn maximum(&self) -> i32 {
+        self.maximum
+    }
+
+    /// Gets the difference between two consecutive values of the control.
+    pub fn step(&self) -> i32 {
+        self.step
+    }
+
+    pub fn default_value(&self) -> i32 {
+        self.default_value
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// Gets the values of a [`Menu`][crate::CameraControlType::Menu] control
+    /// and their names.
+    pub fn menu_items(&self) -> &[(i32, String)] {
+        &self.menu_items
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.is_read_only
+    }
+
+    /// Whether changing the control currently has no effect, e.g. the
+    /// exposure time while the exposure is automatic.
+    pub fn is_inactive(&self) -> bool {
+        self.is_inactive
+    }
+
+    /// Checks that `value` can be set to the control.
+    pub(crate) fn validate(&self, value: i32) -> Result<(), ControlError> {
+        if self.is_read_only {
+            return Err(ControlError::ReadOnly);
+        }
+
+        let is_valid = match self.control_type {
+            CameraControlType::Menu => self.menu_items.iter().any(|(item, _)| *item == value),
+            CameraControlType::Integer | CameraControlType::Boolean => {
+                (self.minimum..=self.maximum).contains(&value)
+            }
+        };
+        if is_valid {
+            Ok(())
+        } else {
+            Err(ControlError::OutOfRange)
+        }
+    }
+}
+
+/// The way the controls of a camera are read and changed.
+pub(crate) trait ControlBackend: std::fmt::Debug {
+    /// Queries the controls of the camera, with their current values.
+    fn controls(&self) -> Vec<CameraControl>;
+
+    /// Queries a single control of the camera, with its current value.
+    fn control(&self, id: CameraControlId) -> Option<CameraControl>;
+
+    /// Sets the value of a control, which was already validated.
+    fn set(&self, id: CameraControlId, value: i32) -> Result<(), ControlError>;
+}
+
+/// The backend of cameras without controls, or whose controls cannot be
+/// accessed, e.g. libcamera devices.
+#[derive(Debug, Default)]
+pub(crate) struct NoControls;
+
+impl ControlBackend for NoControls {
+    fn controls(&self) -> Vec<CameraControl> {
+        Vec::new()
+    }
+
+    fn control(&self, _id: CameraControlId) -> Option<CameraControl> {
+        None
+    }
+
+    fn set(&self, _id: CameraControlId, _value: i32) -> Result<(), ControlError> {
+        Err(ControlError::NotSupported)
+    }
+}
+
+/// A backend keeping the controls in memory, used by the synthetic cameras
+/// of the test backend.
+#[derive(Debug)]
+pub(crate) struct MockControls {
+    controls: RefCell<Vec<CameraControl>>,
+}
+
+impl Default for MockControls {
+    fn default() -> Self {
+        let integer = |id, name: &str, minimum, maximum, step, default_value| CameraControl {
+            id,
+            name: name.to_owned(),
+            control_type: CameraControlType::Integer,
+            minimum,
+            maximum,
+            step,
+            default_value,
+            value: default_value,
+            menu_items: Vec::new(),
+            is_read_only: false,
+            is_inactive: false,
+        };
+        let boolean = |id, name: &str, default_value| CameraControl {
+            control_type: CameraControlType::Boolean,
+            ..integer(id, name, 0, 1, 1, default_value)
+        };
+        let menu = |id, name: &str, items: &[(i32, &str)], default_value| CameraControl {
+            control_type: CameraControlType::Menu,
+            menu_items: items
+                .iter()
+                .map(|(value, name)| (*value, name.to_string()))
+                .collect(),
+            ..integer(
+                id,
+                name,
+                items.first().map_or(0, |item| item.0),
+                items.last().map_or(0, |item| item.0),
+                1,
+                default_value,
+            )
+        };
+
+        let controls = vec![
+            integer(
+                CameraControlId::ExposureTime,
+                "Exposure Time, Absolute",
+                1,
+                5000,
+                1,
+                156,
+            ),
+            integer(CameraControlId::Gain, "Gain", 0, 100, 1, 0),
+            menu(
+                CameraControlId::AutoExposure,
+                "Auto Exposure",
+                &[(1, "Manual Mode"), (3, "Aperture Priority Mode")],
+                3,
+            ),
+            boolean(
+                CameraControlId::AutoWhiteBalance,
+                "White Balance, Automatic",
+                1,
+            ),
+            integer(
+                CameraControlId::WhiteBalanceTemperature,
+                "White Balance Temperature",
+                2800,
+                6500,
+                10,
+                4600,
+            ),
+            integer(CameraControlId::Focus, "Focus, Absolute", 0, 250, 5, 0),
+            boolean(CameraControlId::AutoFocus, "Focus, Automatic Continuous", 1),
+            menu(
+                CameraControlId::PowerLineFrequency,
+                "Power Line Frequency",
+                &[(0, "Disabled"), (1, "50 Hz"), (2, "60 Hz")],
+                1,
+            ),
+            integer(
+                CameraControlId::BacklightCompensation,
+                "Backlight Compensation",
+                0,
+                2,
+                1,
+                1,
+            ),
+        ];
+
+        Self {
+            controls: RefCell::new(controls),
+        }
+    }
+}
+
+impl MockControls {
+    /// Returns a copy of `control`, inactive when its automatic mode is
+    /// enabled.
+    fn with_state(controls: &[CameraControl], control: &CameraControl) -> CameraControl {
+        let value = |id| {
+            controls
+                .iter()
+                .find(|control| control.id == id)
+                .map(|control| control.value)
+        };
+
+        let mut control = control.clone();
+        control.is_inactive = control
+            .id
+            .manual_mode()
+            .is_some_and(|(auto_id, manual)| value(auto_id) != Some(manual));
+        control
+    }
+}
+
+impl ControlBackend for MockControls {
+    fn controls(&self) -> Vec<CameraControl> {
+        let controls = self.controls.borrow();
+        controls
+            .iter()
+            .map(|control| Self::with_state(&controls, control))
+            .collect()
+    }
+
+    fn control(&self, id: CameraControlId) -> Option<CameraControl> {
+        let controls = self.controls.borrow();
+        controls
+            .iter()
+            .find(|control| control.id == id)
+            .map(|control| Self::with_state(&controls, control))
+    }
+
+    fn set(&self, id: CameraControlId, value: i32) -> Result<(), ControlError> {
+        let mut controls = self.controls.borrow_mut();
+        let control = controls
+            .iter_mut()
+            .find(|control| control.id == id)
+            .ok_or(ControlError::NotSupported)?;
+        control.value = value;
+
+        Ok(())
+    }
+}
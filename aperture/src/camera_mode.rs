@@ -0,0 +1,130 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use gst::prelude::*;
+use gtk::glib;
+
+/// A mode supported by a [`Camera`][crate::Camera].
+///
+/// A mode is a combination of encoding, pixel format and resolution together
+/// with the framerates available for it. Modes are obtained with
+/// [`Camera::modes()`][crate::Camera::modes()].
+#[derive(Debug, Clone, PartialEq, Eq, glib::Boxed)]
+#[boxed_type(name = "ApertureCameraMode")]
+pub struct CameraMode {
+    encoding: String,
+    format: Option<gst_video::VideoFormat>,
+    width: i32,
+    height: i32,
+    framerates: Vec<gst::Fraction>,
+}
+
+impl CameraMode {
+    pub(crate) fn new(
+        encoding: &str,
+        format: Option<gst_video::VideoFormat>,
+        width: i32,
+        height: i32,
+        framerates: Vec<gst::Fraction>,
+    ) -> Self {
+        Self {
+            encoding: encoding.to_string(),
+            format,
+            width,
+            height,
+            framerates,
+        }
+    }
+
+    /// Gets the encoding of the mode, e.g. `video/x-raw` or `image/jpeg`.
+    pub fn encoding(&self) -> &str {
+        &self.encoding
+    }
+
+    /// Gets the pixel format of the mode, if the encoding has one.
+    pub fn format(&self) -> Option<gst_video::VideoFormat> {
+        self.format
+    }
+
+    /// Gets the width of the mode, in pixels.
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Gets the height of the mode, in pixels.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Gets the available framerates, from highest to lowest.
+    pub fn framerates(&self) -> &[gst::Fraction] {
+        &self.framerates
+    }
+
+    /// Gets the highest available framerate.
+    pub fn max_framerate(&self) -> Option<gst::Fraction> {
+        self.framerates.first().copied()
+    }
+
+    /// Gets the caps describing this mode, with all of its framerates.
+    pub fn caps(&self) -> gst::Caps {
+        let mut structure = self.structure();
+        match self.framerates.as_slice() {
+            [] => (),
+            [framerate] => structure.set("framerate", *framerate),
+            framerates => structure.set(
+                "framerate",
+                gst::List::from_values(framerates.iter().map(|f| f.to_send_value())),
+            ),
+        }
+
+        gst::Caps::builder_full().structure(structure).build()
+    }
+
+    /// Returns a copy of this mode restricted to the given `framerate`.
+    pub fn with_framerate(&self, framerate: gst::Fraction) -> Self {
+        Self {
+            framerates: vec![framerate],
+            ..self.clone()
+        }
+    }
+
+    /// Gets the caps describing this mode at the given `framerate`.
+    pub fn caps_with_framerate(&self, framerate: gst::Fraction) -> gst::Caps {
+        let mut structure = self.structure();
+        structure.set("framerate", framerate);
+
+        gst::Caps::builder_full().structure(structure).build()
+    }
+
+    /// Whether the mode has the same encoding, format and resolution as
+    /// `other`, regardless of their framerates.
+    pub(crate) fn same_size(&self, other: &Self) -> bool {
+        self.encoding == other.encoding
+            && self.format == other.format
+            && self.width == other.width
+            && self.height == other.height
+    }
+
+    pub(crate) fn add_framerates(&mut self, framerates: &[gst::Fraction]) {
+        self.framerates.extend_from_slice(framerates);
+        self.framerates.sort_by(|a, b| b.cmp(a));
+        self.framerates.dedup();
+    }
+
+    fn structure(&self) -> gst::Structure {
+        let mut structure = gst::Structure::builder(self.encoding.as_str())
+            .field("width", self.width)
+            .field("height", self.height)
+            .build();
+        if let Some(format) = self.format {
+            structure.set("format", format.to_str());
+        }
+
+        structure
+    }
+}
+
+impl std::fmt::Display for CameraMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}×{}", self.width, self.height)
+    }
+}
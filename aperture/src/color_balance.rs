@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+use gst::prelude::*;
+use gtk::glib;
+
+/// The colour balance of the camera feed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Balance {
+    /// From `-1.0` to `1.0`.
+    pub brightness: f64,
+    /// From `0.0` to `2.0`.
+    pub contrast: f64,
+    /// From `0.0` to `2.0`.
+    pub saturation: f64,
+    /// From `-1.0` to `1.0`.
+    pub hue: f64,
+    /// How much colours are shifted towards red when positive, or towards
+    /// blue when negative, from `-128` to `128`.
+    pub tint: i32,
+}
+
+impl Default for Balance {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+            hue: 0.0,
+            tint: 0,
+        }
+    }
+}
+
+/// Applies a [`Balance`] to the camera feed. The balance is kept when the
+/// camera source, and thus the bin applying it, is recreated.
+#[derive(Debug, Default)]
+pub(crate) struct ColorBalance {
+    videobalance: RefCell<Option<gst::Element>>,
+    balance: Cell<Balance>,
+    tint: Arc<AtomicI32>,
+}
+
+impl ColorBalance {
+    /// Creates the bin applying the balance, replacing the previous one.
+    pub fn create_bin(&self) -> Result<gst::Element, glib::BoolError> {
+        let bin = gst::Bin::new();
+
+        // Not every camera format is supported by videobalance.
+        let videoconvert = gst::ElementFactory::make("videoconvert").build()?;
+        let videobalance = gst::ElementFactory::make("videobalance").build()?;
+
+        bin.add_many([&videoconvert, &videobalance]).unwrap();
+        videoconvert.link(&videobalance).unwrap();
+
+        let src_pad = videobalance.static_pad("src").unwrap();
+        let tint = self.tint.clone();
+        src_pad.add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+            let amount = tint.load(Ordering::Relaxed);
+            if amount == 0 {
+                return gst::PadProbeReturn::Ok;
+            }
+
+            let Some(gst::PadProbeData::Buffer(ref mut buffer)) = info.data else {
+                return gst::PadProbeReturn::Ok;
+            };
+            let Some(video_info) = pad
+                .current_caps()
+                .and_then(|caps| gst_video::VideoInfo::from_caps(&caps).ok())
+            else {
+                return gst::PadProbeReturn::Ok;
+            };
+
+            let buffer = buffer.make_mut();
+            match gst_video::VideoFrameRef::from_buffer_ref_writable(buffer, &video_info) {
+                Ok(mut frame) => apply_tint(&mut frame, amount),
+                Err(err) => log::error!("Could not map frame to tint it: {err}"),
+            }
+
+            gst::PadProbeReturn::Ok
+        });
+
+        let sink_pad = videoconvert.static_pad("sink").unwrap();
+        let ghost_pad = gst::GhostPad::with_target(&sink_pad).unwrap();
+        ghost_pad.set_active(true).unwrap();
+        bin.add_pad(&ghost_pad).unwrap();
+
+        let ghost_pad = gst::GhostPad::with_target(&src_pad).unwrap();
+        ghost_pad.set_active(true).unwrap();
+        bin.add_pad(&ghost_pad).unwrap();
+
+        self.videobalance.replace(Some(videobalance));
+        self.apply();
+
+        Ok(bin.upcast())
+    }
+
+    pub fn set(&self, balance: Balance) {
+        if balance != self.balance.replace(balance) {
+            self.apply();
+        }
+    }
+
+    fn apply(&self) {
+        let balance = self.balance.get();
+
+        if let Some(videobalance) = self.videobalance.borrow().as_ref() {
+            videobalance.set_property("brightness", balance.brightness.clamp(-1.0, 1.0));
+            videobalance.set_property("contrast", balance.contrast.clamp(0.0, 2.0));
+            videobalance.set_property("saturation", balance.saturation.clamp(0.0, 2.0));
+            videobalance.set_property("hue", balance.hue.clamp(-1.0, 1.0));
+        }
+        self.tint
+            .store(balance.tint.clamp(-128, 128), Ordering::Relaxed);
+    }
+}
+
+/// Shifts the colours of `frame` towards red when `amount` is positive, or
+/// towards blue when it is negative.
+fn apply_tint(frame: &mut gst_video::VideoFrameRef<&mut gst::BufferRef>, amount: i32) {
+    let info = frame.format_info();
+    // The components are R, G, B for RGB formats and Y, U, V for YUV ones.
+    if info.is_rgb() {
+        shift_component(frame, 0, amount);
+        shift_component(frame, 2, -amount);
+    } else if info.is_yuv() {
+        shift_component(frame, 1, -amount);
+        shift_component(frame, 2, amount);
+    }
+}
+
+/// Adds `delta` to the 8 bit `component` of every pixel of `frame`.
+fn shift_component(
+    frame: &mut gst_video::VideoFrameRef<&mut gst::BufferRef>,
+    component: u8,
+    delta: i32,
+) {
+    let info = frame.format_info();
+    let index = component as usize;
+    if info.depth()[index] != 8 {
+        return;
+    }
+
+    let plane = info.plane()[index];
+    let offset = info.poffset()[index] as usize;
+    let pixel_stride = info.pixel_stride()[index] as usize;
+    let width = info.scale_width(component, frame.width()) as usize;
+    let height = info.scale_height(component, frame.height()) as usize;
+    let stride = frame.plane_stride()[plane as usize] as usize;
+
+    let Ok(data) = frame.plane_data_mut(plane) else {
+        return;
+    };
+    for row in data.chunks_mut(stride).take(height) {
+        for x in 0..width {
+            if let Some(value) = row.get_mut(offset + x * pixel_stride) {
+                *value = (i32::from(*value) + delta).clamp(0, 255) as u8;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_tint() {
+        gst::init().unwrap();
+
+        let info = gst_video::VideoInfo::builder(gst_video::VideoFormat::Rgba, 2, 2)
+            .build()
+            .unwrap();
+        let mut buffer = gst::Buffer::with_size(info.size()).unwrap();
+        {
+            let buffer = buffer.get_mut().unwrap();
+            buffer.map_writable().unwrap().fill(128);
+
+            let mut frame =
+                gst_video::VideoFrameRef::from_buffer_ref_writable(buffer, &info).unwrap();
+            apply_tint(&mut frame, 20);
+        }
+
+        let map = buffer.map_readable().unwrap();
+        for pixel in map.chunks(4) {
+            assert_eq!(pixel, [148, 128, 108, 128]);
+        }
+    }
+}
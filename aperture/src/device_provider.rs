@@ -3,6 +3,7 @@ use std::cell::RefCell;
 use std::collections::HashSet;
 use std::os::fd::{AsRawFd, OwnedFd};
 use std::os::unix::io::RawFd;
+use std::path::Path;
 use std::sync::Once;
 
 use gst::prelude::*;
@@ -14,6 +15,9 @@ use crate::utils;
 
 static STARTED: Once = Once::new();
 
+/// The built-in backends, in order of preference.
+const BACKENDS: [&str; 2] = ["pipewiredeviceprovider", "v4l2deviceprovider"];
+
 type ProviderCallback = Box<dyn Fn(&crate::Camera) -> bool + 'static>;
 
 mod imp {
@@ -27,8 +31,12 @@ mod imp {
     #[derive(Default, Properties)]
     #[properties(wrapper_type = super::DeviceProvider)]
     pub struct DeviceProvider {
+        /// The backend in use, set once started.
         pub inner: OnceCell<gst::DeviceProvider>,
+        /// The candidate backends, in order of preference.
+        pub backends: RefCell<Vec<gst::DeviceProvider>>,
         pub cameras: RefCell<Vec<crate::Camera>>,
+        pub microphones: OnceCell<gio::ListStore>,
         pub bus_watch: OnceCell<gst::bus::BusWatchGuard>,
 
         pub fd: RefCell<Option<OwnedFd>>,
@@ -37,6 +45,8 @@ mod imp {
 
         #[property(get = Self::started)]
         pub started: std::marker::PhantomData<bool>,
+        #[property(get = Self::backend)]
+        pub backend: std::marker::PhantomData<Option<String>>,
     }
 
     impl DeviceProvider {
@@ -47,23 +57,57 @@ mod imp {
             self.obj().emit_camera_added(&camera);
         }
 
+        pub fn microphones(&self) -> &gio::ListStore {
+            self.microphones
+                .get_or_init(gio::ListStore::new::<crate::Microphone>)
+        }
+
+        pub fn append_microphone(&self, microphone: crate::Microphone) {
+            let microphones = self.microphones();
+            let is_duplicate = microphones
+                .iter::<crate::Microphone>()
+                .filter_map(Result::ok)
+                .any(|m| {
+                    m.device() == microphone.device()
+                        || (m.target_object().is_some()
+                            && m.target_object() == microphone.target_object())
+                });
+            if !is_duplicate {
+                microphones.append(&microphone);
+            }
+        }
+
+        pub fn remove_microphone(&self, device: &gst::Device) {
+            let microphones = self.microphones();
+            if let Some(pos) = microphones
+                .iter::<crate::Microphone>()
+                .position(|m| m.is_ok_and(|m| &m.device() == device))
+            {
+                microphones.remove(pos as u32);
+            }
+        }
+
         fn started(&self) -> bool {
             STARTED.is_completed()
         }
 
+        fn backend(&self) -> Option<String> {
+            self.inner.get().map(backend_name)
+        }
+
         pub fn has_camera(&self, camera: &crate::Camera) -> bool {
             self.cameras.borrow().iter().any(|c| {
-                c.device() == camera.device() || c.target_object() == camera.target_object()
+                c.device() == camera.device()
+                    || (c.target_object().is_some() && c.target_object() == camera.target_object())
             })
         }
 
         pub fn remove(&self, device: crate::Camera) {
             let guard = self.cameras.borrow();
-            let Some((pos, _)) = guard
-                .iter()
-                .enumerate()
-                .find(|(_idx, x)| x.target_object() == device.target_object())
-            else {
+            let Some((pos, _)) = guard.iter().enumerate().find(|(_idx, x)| {
+                x.device() == device.device()
+                    || (x.target_object().is_some() && x.target_object() == device.target_object())
+            }) else {
                 log::error!(
                     "Tried to remove camera with target-object {:?} but it wasn't in the vec?",
                     device.target_object()
@@ -107,17 +151,31 @@ mod imp {
 
             crate::ensure_init();
 
-            if let Some(provider) = gst::DeviceProviderFactory::by_name("pipewiredeviceprovider") {
-                self.inner.set(provider).unwrap();
+            let mut backends = self.backends.borrow_mut();
+            if let Some(n_cameras) = crate::test_provider::cameras_from_env() {
+                log::debug!("Using the test backend with {n_cameras} cameras");
+                let provider = crate::test_provider::TestDeviceProvider::new(n_cameras);
+                backends.push(provider.upcast());
+            } else {
+                backends.extend(BACKENDS.into_iter().filter_map(|name| {
+                    let provider = gst::DeviceProviderFactory::by_name(name);
+                    if provider.is_none() {
+                        log::debug!("Device provider {name} is not available");
+                    }
+                    provider
+                }));
             }
         }
 
         fn dispose(&self) {
-            let inner = self.inner.get().unwrap();
-            if inner.is_started() {
-                inner.stop();
+            for backend in self.backends.borrow().iter() {
+                if backend.is_started() {
+                    backend.stop();
+                }
+                if backend.has_property("fd", None) {
+                    backend.set_property("fd", -1);
+                }
             }
-            inner.set_property("fd", -1);
         }
 
         fn signals() -> &'static [glib::subclass::Signal] {
@@ -142,6 +200,30 @@ glib::wrapper! {
     /// It is used to find and monitor cameras that can be used in Aperture. It also handles the
     /// creation of [`Camera`][crate::Camera] objects.
     ///
+    /// Cameras are provided by one of several backends. When started, the backends are tried in
+    /// order: the Pipewire device provider, the V4L2 device provider and then the ones registered
+    /// with [`register_backend()`][crate::DeviceProvider::register_backend()]. The first backend
+    /// that has cameras is used, otherwise the first one that could be started.
+    ///
+    /// The microphones of the backend in use are listed by
+    /// [`microphones()`][crate::DeviceProvider::microphones()]. Note that a Pipewire remote set
+    /// with [`set_fd()`][crate::DeviceProvider::set_fd()] usually only provides cameras.
+    ///
+    /// ## Properties
+    ///
+    ///
+    /// #### `started`
+    ///  Whether the device provider has been started.
+    ///
+    ///  Readable
+    ///
+    ///
+    /// #### `backend`
+    ///  The name of the backend in use, e.g. `pipewiredeviceprovider`. It is `None` until the
+    ///  device provider is started.
+    ///
+    ///  Readable | Nullable
+    ///
     /// ## Signals
     ///
     ///
@@ -199,12 +281,9 @@ impl DeviceProvider {
 
         let imp = self.imp();
 
-        let Some(provider) = self.imp().inner.get() else {
-            return Err(crate::ProviderError::MissingPlugin(
-                "pipewiredeviceprovider",
-            ));
-        };
-        provider.start()?;
+        let provider = self.start_backend()?;
+        imp.inner.set(provider.clone()).unwrap();
+        log::debug!("Using backend {}", backend_name(&provider));
 
         let mut seen = HashSet::new();
         let mut cameras = provider
@@ -214,7 +293,9 @@ impl DeviceProvider {
             .map(crate::Camera::new)
             .filter(|d| !is_ir_camera(d))
             .collect::<Vec<_>>();
-        cameras.retain(|item| seen.insert(item.target_object()));
+        // Devices without a `target-object`, e.g. test cameras, are never
+        // duplicates.
+        cameras.retain(|item| item.target_object().map_or(true, |id| seen.insert(id)));
         let n_items = cameras.len() as u32;
         cameras.iter().for_each(|camera| {
             log::debug!(
@@ -228,6 +309,11 @@ impl DeviceProvider {
         self.imp().cameras.replace(cameras);
         self.items_changed(0, 0, n_items);
 
+        for device in provider.devices().iter().filter(|d| is_microphone(d)) {
+            log::debug!("Microphone found: {}", device.display_name());
+            imp.append_microphone(crate::Microphone::new(device));
+        }
+
         let bus = provider.bus();
         let watch = bus
             .add_watch_local(
@@ -243,6 +329,61 @@ impl DeviceProvider {
         let _ = imp.default_cb.set(Box::new(f));
 
         self.notify_started();
+        self.notify_backend();
+
+        Ok(())
+    }
+
+    /// Starts the first backend that has cameras, or the first one that could
+    /// be started if none has. The remaining backends are stopped.
+    fn start_backend(&self) -> Result<gst::DeviceProvider, crate::ProviderError> {
+        let backends = self.imp().backends.borrow().clone();
+
+        let mut candidate = None;
+        let mut error = None;
+        for backend in backends {
+            if let Err(err) = backend.start() {
+                log::debug!("Could not start backend {}: {err}", backend_name(&backend));
+                error = Some(err);
+                continue;
+            }
+
+            if backend.devices().iter().any(is_camera) {
+                if let Some(candidate) = candidate.replace(backend) {
+                    candidate.stop();
+                }
+                break;
+            } else if candidate.is_none() {
+                candidate = Some(backend);
+            } else {
+                backend.stop();
+            }
+        }
+
+        match (candidate, error) {
+            (Some(backend), _) => Ok(backend),
+            (None, Some(err)) => Err(err.into()),
+            (None, None) => Err(crate::ProviderError::MissingPlugin(BACKENDS[0])),
+        }
+    }
+
+    /// Registers an additional backend.
+    ///
+    /// Registered backends are tried after the built-in ones, in order of
+    /// registration. This must be called before the device provider is
+    /// started.
+    pub fn register_backend(
+        &self,
+        backend: &impl IsA<gst::DeviceProvider>,
+    ) -> Result<(), crate::ProviderError> {
+        if STARTED.is_completed() {
+            return Err(crate::ProviderError::AlreadyStarted);
+        }
+
+        self.imp()
+            .backends
+            .borrow_mut()
+            .push(backend.as_ref().clone());
 
         Ok(())
     }
@@ -274,6 +415,82 @@ impl DeviceProvider {
         self.item(position).and_downcast()
     }
 
+    /// Adds a synthetic camera to the test backend.
+    ///
+    /// The test backend is used when the `APERTURE_TEST_CAMERAS` environment
+    /// variable is set, its value being the number of synthetic cameras
+    /// available at startup. The cameras are backed by `videotestsrc`, or by
+    /// the image at `image` if set, and the microphone by `audiotestsrc`. The
+    /// default caps of the synthetic cameras can be overridden with the
+    /// `APERTURE_TEST_CAMERA_CAPS` environment variable and the default image
+    /// with `APERTURE_TEST_CAMERA_IMAGE`.
+    ///
+    /// If the device provider is started, the camera will be announced via the
+    /// [`camera-added`](#camera-added) signal.
+    pub fn add_test_camera(
+        &self,
+        display_name: &str,
+        caps: Option<&gst::Caps>,
+        image: Option<&Path>,
+    ) -> Result<(), crate::ProviderError> {
+        let provider = self.test_provider()?;
+        provider.add_camera(display_name, caps, image);
+
+        Ok(())
+    }
+
+    /// Removes a synthetic camera from the test backend.
+    ///
+    /// If the device provider is started, the camera will be announced via the
+    /// [`camera-removed`](#camera-removed) signal.
+    pub fn remove_test_camera(&self, camera: &crate::Camera) -> Result<(), crate::ProviderError> {
+        let provider = self.test_provider()?;
+        provider.remove_device(&camera.device());
+
+        Ok(())
+    }
+
+    fn test_provider(
+        &self,
+    ) -> Result<crate::test_provider::TestDeviceProvider, crate::ProviderError> {
+        let backends = self.imp().backends.borrow();
+        backends
+            .iter()
+            .find_map(|backend| {
+                backend
+                    .downcast_ref::<crate::test_provider::TestDeviceProvider>()
+                    .cloned()
+            })
+            .ok_or(crate::ProviderError::NotTestBackend)
+    }
+
+    /// Gets the microphones available in the backend in use.
+    ///
+    /// # Returns
+    ///
+    /// a [`gio::ListModel`][gtk::gio::ListModel] of
+    /// [`Microphone`][crate::Microphone]s, which is empty until the device
+    /// provider is started.
+    pub fn microphones(&self) -> gio::ListModel {
+        self.imp().microphones().clone().upcast()
+    }
+
+    /// Gets the microphone to use when none is selected, `None` meaning the
+    /// default audio source of the system.
+    pub(crate) fn default_microphone(&self) -> Option<crate::Microphone> {
+        // The synthetic cameras come with a synthetic microphone.
+        let is_test_backend = self
+            .imp()
+            .inner
+            .get()
+            .is_some_and(|backend| backend.is::<crate::test_provider::TestDeviceProvider>());
+        if is_test_backend {
+            self.imp().microphones().item(0).and_downcast()
+        } else {
+            None
+        }
+    }
+
     /// Set a valid file description to load and monitor cameras from.
     ///
     /// This file descriptor should point to a valid Pipewire remote where
@@ -287,7 +504,13 @@ impl DeviceProvider {
             return Err(crate::PipewireError::ProvidedStarted);
         }
         let raw_fd = fd.as_raw_fd();
-        let provider = self.imp().inner.get().unwrap();
+        let backends = self.imp().backends.borrow();
+        let Some(provider) = backends
+            .iter()
+            .find(|backend| backend_name(backend) == BACKENDS[0])
+        else {
+            return Err(crate::PipewireError::NotAvailable);
+        };
         log::debug!("Starting device provider with file descriptor: {raw_fd}");
         if provider.has_property("fd", Some(RawFd::static_type())) {
             provider.set_property("fd", raw_fd);
@@ -367,6 +590,9 @@ impl DeviceProvider {
                                 );
                                 imp.append(device);
                             }
+                        } else if is_microphone(&device) {
+                            log::debug!("Microphone added: {}", device.display_name());
+                            imp.append_microphone(crate::Microphone::new(&device));
                         };
                     }
                 }
@@ -385,6 +611,9 @@ impl DeviceProvider {
                                     }
                                 };
                             }
+                        } else if is_microphone(&device) {
+                            log::debug!("Microphone removed: {}", device.display_name());
+                            imp.remove_microphone(&device);
                         };
                     }
                 }
@@ -400,6 +629,17 @@ fn is_ir_camera(device: &crate::Camera) -> bool {
         || device.display_name().contains("IR")
 }
 
+fn backend_name(backend: &gst::DeviceProvider) -> String {
+    backend
+        .factory()
+        .map(|factory| factory.name().to_string())
+        .unwrap_or_else(|| backend.name().to_string())
+}
+
 fn is_camera(device: &gst::Device) -> bool {
     device.device_class() == "Video/Source"
 }
+
+fn is_microphone(device: &gst::Device) -> bool {
+    device.device_class() == "Audio/Source"
+}
@@ -98,3 +98,267 @@ impl<S: AsRef<str>> From<S> for CameraLocation {
         }
     }
 }
+
+/// Describes the formats in which a [`Viewfinder`][crate::Viewfinder] can save
+/// pictures.
+#[derive(Default, Debug, Copy, Clone, glib::Enum, PartialEq)]
+#[repr(u32)]
+#[enum_type(name = "AperturePictureFormat")]
+pub enum PictureFormat {
+    /// JPEG, encoded with `jpegenc`.
+    #[default]
+    Jpeg,
+    /// Lossless PNG, encoded with `pngenc`.
+    Png,
+    /// WebP, encoded with `webpenc`.
+    Webp,
+    /// AVIF, encoded with any AV1 encoder and muxed with `avifmux`.
+    Avif,
+    /// Lossless TIFF, encoded with `avenc_tiff`.
+    Tiff,
+}
+
+impl PictureFormat {
+    /// Whether the GStreamer elements needed to save pictures in this format
+    /// are available.
+    pub fn is_supported(&self) -> bool {
+        let has_encoder = has_factory(gst::ElementFactoryType::ENCODER, &self.encoder_caps());
+
+        match self {
+            Self::Avif => has_encoder && gst::ElementFactory::find("avifmux").is_some(),
+            _ => has_encoder,
+        }
+    }
+
+    /// The usual extension of the files in this format, e.g. `jpeg`.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpeg",
+            Self::Png => "png",
+            Self::Webp => "webp",
+            Self::Avif => "avif",
+            Self::Tiff => "tiff",
+        }
+    }
+
+    /// The caps of the saved pictures.
+    pub(crate) fn caps(&self) -> gst::Caps {
+        match self {
+            Self::Jpeg => gst::Caps::builder("image/jpeg").build(),
+            Self::Png => gst::Caps::builder("image/png").build(),
+            Self::Webp => gst::Caps::builder("image/webp").build(),
+            Self::Avif => gst::Caps::builder("image/avif").build(),
+            Self::Tiff => gst::Caps::builder("image/tiff").build(),
+        }
+    }
+
+    /// The caps produced by the encoder, these differ from [`Self::caps`] for
+    /// formats that need a muxer.
+    pub(crate) fn encoder_caps(&self) -> gst::Caps {
+        match self {
+            Self::Avif => gst::Caps::builder("video/x-av1").build(),
+            _ => self.caps(),
+        }
+    }
+}
+
+/// Describes the formats in which a [`Viewfinder`][crate::Viewfinder] can
+/// record videos.
+#[derive(Default, Debug, Copy, Clone, glib::Enum, PartialEq)]
+#[repr(u32)]
+#[enum_type(name = "ApertureVideoFormat")]
+pub enum VideoFormat {
+    /// WebM with VP8 video and Vorbis audio.
+    #[default]
+    Vp8Webm,
+    /// WebM with VP9 video and Opus audio.
+    Vp9Webm,
+    /// WebM with AV1 video and Opus audio.
+    Av1Webm,
+    /// MP4 with H.264 video and AAC audio.
+    H264Mp4,
+    /// Matroska with H.264 video and Opus audio.
+    H264Matroska,
+}
+
+impl VideoFormat {
+    /// Whether the GStreamer elements needed to record videos in this format
+    /// are available.
+    pub fn is_supported(&self) -> bool {
+        has_factory(gst::ElementFactoryType::MUXER, &self.container_caps())
+            && has_factory(gst::ElementFactoryType::ENCODER, &self.video_caps())
+            && has_factory(gst::ElementFactoryType::ENCODER, &self.audio_caps())
+    }
+
+    pub(crate) fn container_caps(&self) -> gst::Caps {
+        match self {
+            Self::Vp8Webm | Self::Vp9Webm | Self::Av1Webm => {
+                gst::Caps::builder("video/webm").build()
+            }
+            Self::H264Mp4 => gst::Caps::builder("video/quicktime")
+                .field("variant", "iso")
+                .build(),
+            Self::H264Matroska => gst::Caps::builder("video/x-matroska").build(),
+        }
+    }
+
+    pub(crate) fn video_caps(&self) -> gst::Caps {
+        match self {
+            Self::Vp8Webm => gst::Caps::builder("video/x-vp8").build(),
+            Self::Vp9Webm => gst::Caps::builder("video/x-vp9").build(),
+            Self::Av1Webm => gst::Caps::builder("video/x-av1").build(),
+            Self::H264Mp4 | Self::H264Matroska => gst::Caps::builder("video/x-h264").build(),
+        }
+    }
+
+    pub(crate) fn audio_caps(&self) -> gst::Caps {
+        match self {
+            Self::Vp8Webm => gst::Caps::builder("audio/x-vorbis").build(),
+            Self::Vp9Webm | Self::Av1Webm | Self::H264Matroska => {
+                gst::Caps::builder("audio/x-opus").build()
+            }
+            Self::H264Mp4 => gst::Caps::builder("audio/mpeg")
+                .field("mpegversion", 4i32)
+                .build(),
+        }
+    }
+}
+
+/// Describes what a [`Viewfinder`][crate::Viewfinder] does when a recording
+/// reaches its maximum duration or size.
+#[derive(Default, Debug, Copy, Clone, glib::Enum, PartialEq)]
+#[repr(u32)]
+#[enum_type(name = "ApertureRecordingLimitAction")]
+pub enum RecordingLimitAction {
+    /// The recording is stopped.
+    #[default]
+    Stop,
+    /// The recording continues in a new file, numbered after the previous
+    /// one.
+    Split,
+}
+
+/// Describes the effect applied by a [`Viewfinder`][crate::Viewfinder] to the
+/// camera feed, and thus to pictures and recordings.
+#[derive(Default, Debug, Copy, Clone, glib::Enum, PartialEq)]
+#[repr(u32)]
+#[enum_type(name = "ApertureVideoEffect")]
+pub enum VideoEffect {
+    /// No effect.
+    #[default]
+    None,
+    /// Black and white.
+    Mono,
+    /// Brown tones, like old photographs.
+    Sepia,
+    /// Deeper shadows and brighter highlights.
+    HighContrast,
+    /// Saturated colours.
+    Vivid,
+    /// Colours shifted towards red.
+    Warm,
+    /// Colours shifted towards blue.
+    Cool,
+}
+
+impl VideoEffect {
+    pub(crate) fn balance(&self) -> crate::color_balance::Balance {
+        let balance = crate::color_balance::Balance::default();
+        match self {
+            Self::None => balance,
+            Self::Mono => crate::color_balance::Balance {
+                saturation: 0.0,
+                ..balance
+            },
+            Self::Sepia => crate::color_balance::Balance {
+                saturation: 0.0,
+                tint: 24,
+                ..balance
+            },
+            Self::HighContrast => crate::color_balance::Balance {
+                contrast: 1.5,
+                ..balance
+            },
+            Self::Vivid => crate::color_balance::Balance {
+                contrast: 1.1,
+                saturation: 1.6,
+                ..balance
+            },
+            Self::Warm => crate::color_balance::Balance {
+                tint: 12,
+                ..balance
+            },
+            Self::Cool => crate::color_balance::Balance {
+                tint: -12,
+                ..balance
+            },
+        }
+    }
+}
+
+/// Identifies a hardware control of a [`Camera`][crate::Camera].
+#[derive(Debug, Copy, Clone, glib::Enum, PartialEq, Eq, Hash)]
+#[repr(u32)]
+#[enum_type(name = "ApertureCameraControlId")]
+pub enum CameraControlId {
+    /// The exposure time, in units of 100 µs.
+    ExposureTime,
+    Gain,
+    /// The exposure mode, a menu of manual and automatic modes.
+    AutoExposure,
+    AutoWhiteBalance,
+    /// The white balance, in Kelvin.
+    WhiteBalanceTemperature,
+    Focus,
+    AutoFocus,
+    /// A menu of the frequencies of the power line, to avoid flickering.
+    PowerLineFrequency,
+    BacklightCompensation,
+}
+
+impl CameraControlId {
+    pub const ALL: [Self; 9] = [
+        Self::ExposureTime,
+        Self::Gain,
+        Self::AutoExposure,
+        Self::AutoWhiteBalance,
+        Self::WhiteBalanceTemperature,
+        Self::Focus,
+        Self::AutoFocus,
+        Self::PowerLineFrequency,
+        Self::BacklightCompensation,
+    ];
+
+    /// The automatic control under which `self` is inactive, and the value
+    /// for which it is not automatic.
+    pub(crate) fn manual_mode(&self) -> Option<(Self, i32)> {
+        match self {
+            // V4L2_EXPOSURE_MANUAL
+            Self::ExposureTime => Some((Self::AutoExposure, 1)),
+            Self::WhiteBalanceTemperature => Some((Self::AutoWhiteBalance, 0)),
+            Self::Focus => Some((Self::AutoFocus, 0)),
+            _ => None,
+        }
+    }
+}
+
+/// Describes the values of a camera control.
+#[derive(Default, Debug, Copy, Clone, glib::Enum, PartialEq)]
+#[repr(u32)]
+#[enum_type(name = "ApertureCameraControlType")]
+pub enum CameraControlType {
+    /// A value between a minimum and a maximum.
+    #[default]
+    Integer,
+    /// `0` or `1`.
+    Boolean,
+    /// One of the values of the menu items of the control.
+    Menu,
+}
+
+/// Whether there is an element of type `type_` producing `caps`.
+fn has_factory(type_: gst::ElementFactoryType, caps: &gst::Caps) -> bool {
+    let factories = gst::ElementFactory::factories_with_type(type_, gst::Rank::Marginal);
+
+    !gst::ElementFactory::list_filter(&factories, caps, gst::PadDirection::Src, false).is_empty()
+}
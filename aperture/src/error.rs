@@ -7,6 +7,10 @@ use gtk::glib;
 pub enum ProviderError {
     MissingPlugin(&'static str),
     BoolError(glib::BoolError),
+    /// The operation requires the test backend, which is not in use.
+    NotTestBackend,
+    /// The operation must be done before the device provider is started.
+    AlreadyStarted,
 }
 
 impl From<glib::BoolError> for ProviderError {
@@ -22,6 +26,8 @@ impl std::fmt::Display for ProviderError {
         match self {
             Self::MissingPlugin(plugin) => write!(f, "Missing gstreamer plugin {plugin}"),
             Self::BoolError(err) => write!(f, "{err}"),
+            Self::NotTestBackend => f.write_str("The test backend is not in use"),
+            Self::AlreadyStarted => f.write_str("The device provider has already been started"),
         }
     }
 }
@@ -47,6 +53,26 @@ pub enum CaptureError {
     /// The [`Viewfinder`][crate::Viewfinder] is not in the
     /// [`Ready`][crate::ViewfinderState::Ready] state.
     NotReady,
+    /// No recording in progress was found to pause.
+    NoRecordingToPause,
+    /// No paused recording was found to resume.
+    NoRecordingToResume,
+    /// No loop recording was found to save.
+    NoLoopRecording,
+    /// A burst is being taken and should finish before taking another one.
+    BurstInProgress,
+    /// The elements needed to take a burst are missing.
+    BurstUnavailable,
+    /// No frame could be taken from the camera feed.
+    FrameUnavailable,
+    /// The branch is already part of a pipeline, or has no sink pad.
+    InvalidBranch,
+    /// The branch to remove was not added to the viewfinder.
+    NoBranchToRemove,
+    /// The frame processor was not added to the viewfinder.
+    NoFrameProcessor,
+    /// The elements needed to take a picture while recording are missing.
+    StillUnavailable,
 }
 
 impl std::error::Error for CaptureError {}
@@ -58,12 +84,53 @@ impl std::fmt::Display for CaptureError {
             Self::RecordingInProgress => f.write_str("Operation in progress: Video recording"),
             Self::SnapshotInProgress => f.write_str("Operation in progress: Take Picture"),
             Self::NoRecordingToStop => f.write_str("There is no recording to stop"),
+            Self::NoRecordingToPause => f.write_str("There is no recording to pause"),
+            Self::NoRecordingToResume => f.write_str("There is no paused recording to resume"),
+            Self::NoLoopRecording => f.write_str("There is no loop recording to save"),
+            Self::BurstInProgress => f.write_str("Operation in progress: Take Burst"),
+            Self::BurstUnavailable => f.write_str("Could not create the elements to take a burst"),
+            Self::FrameUnavailable => f.write_str("Could not get a frame from the camera"),
+            Self::InvalidBranch => f.write_str("The branch cannot be added to the viewfinder"),
+            Self::NoBranchToRemove => f.write_str("There is no such branch to remove"),
+            Self::NoFrameProcessor => f.write_str("There is no such frame processor"),
+            Self::StillUnavailable => {
+                f.write_str("Could not create the elements to take a picture while recording")
+            }
             Self::StopRecordingInProgress => f.write_str("Operation in progress: Stop recording"),
             Self::CameraDisconnected => f.write_str("The current camera was disconnected"),
         }
     }
 }
 
+/// Describes the possible error codes of a [`Camera`][crate::Camera] while
+/// changing its controls.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, glib::ErrorDomain)]
+#[error_domain(name = "ApertureControlError")]
+pub enum ControlError {
+    /// The camera does not have this control.
+    NotSupported,
+    /// The value is not within the range of the control, or not one of its
+    /// menu items.
+    OutOfRange,
+    /// The control cannot be changed.
+    ReadOnly,
+    /// The device refused the change.
+    Failed,
+}
+
+impl std::error::Error for ControlError {}
+
+impl std::fmt::Display for ControlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotSupported => f.write_str("The camera does not support this control"),
+            Self::OutOfRange => f.write_str("The value is out of the range of the control"),
+            Self::ReadOnly => f.write_str("The control is read-only"),
+            Self::Failed => f.write_str("The camera could not change the control"),
+        }
+    }
+}
+
 /// Describes the possible error codes of a [`Viewfinder`][crate::Viewfinder]
 /// from Pipewire.
 #[derive(Debug, Eq, PartialEq, Clone, Copy, glib::ErrorDomain)]
@@ -73,6 +140,8 @@ pub enum PipewireError {
     OldVersion,
     /// The device provider has already been started.
     ProvidedStarted,
+    /// The Pipewire device provider is not available.
+    NotAvailable,
 }
 
 impl std::error::Error for PipewireError {}
@@ -82,6 +151,7 @@ impl std::fmt::Display for PipewireError {
         match self {
             Self::OldVersion => f.write_str("Current pipewire version is too old"),
             Self::ProvidedStarted => f.write_str("The device provided has already been started"),
+            Self::NotAvailable => f.write_str("The pipewire device provider is not available"),
         }
     }
 }
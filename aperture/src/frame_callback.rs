@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use std::sync::Mutex;
+use std::time::Duration;
+
+use gst::prelude::*;
+use gtk::glib;
+
+/// A frame of the camera feed passed to the callbacks added with
+/// [`fn@crate::Viewfinder::add_frame_callback`].
+pub type Frame = gst_video::VideoFrame<gst_video::video_frame::Readable>;
+
+/// Creates a branch of the viewfinder stream calling `callback` with frames
+/// converted to `format`, at most one every `interval`.
+///
+/// Frames are dropped before being converted, so that throttling the
+/// callback also saves the cost of the conversion.
+pub(crate) fn create_frame_callback_bin<F>(
+    format: gst_video::VideoFormat,
+    interval: Duration,
+    callback: F,
+) -> Result<gst::Element, glib::BoolError>
+where
+    F: Fn(&Frame) + Send + Sync + 'static,
+{
+    let bin = gst::Bin::new();
+
+    let videoconvert = gst::ElementFactory::make("videoconvert").build()?;
+    // The branch is already leaky, the sink only keeps the latest frame.
+    let appsink = gst::ElementFactory::make("appsink")
+        .property(
+            "caps",
+            gst_video::VideoCapsBuilder::new().format(format).build(),
+        )
+        .property("max-buffers", 1u32)
+        .property("drop", true)
+        .property("sync", false)
+        .property("async", false)
+        .build()?
+        .downcast::<gst_app::AppSink>()
+        .unwrap();
+
+    bin.add_many([&videoconvert, appsink.upcast_ref()]).unwrap();
+    videoconvert.link(&appsink).unwrap();
+
+    let pad = videoconvert.static_pad("sink").unwrap();
+    let interval = gst::ClockTime::try_from(interval).unwrap_or(gst::ClockTime::ZERO);
+    // The timestamp from which the next frame is passed to the callback.
+    let next_pts = Mutex::new(None::<gst::ClockTime>);
+    pad.add_probe(gst::PadProbeType::BUFFER, move |_, info| {
+        let Some(gst::PadProbeData::Buffer(ref buffer)) = info.data else {
+            return gst::PadProbeReturn::Ok;
+        };
+
+        let mut next_pts = next_pts.lock().unwrap();
+        let pts = buffer.pts();
+        let is_due = match *next_pts {
+            Some(next_pts) => pts.is_some_and(|pts| pts >= next_pts),
+            None => true,
+        };
+        if !is_due {
+            return gst::PadProbeReturn::Drop;
+        }
+        *next_pts = pts.map(|pts| pts + interval);
+
+        gst::PadProbeReturn::Ok
+    });
+
+    appsink.set_callbacks(
+        gst_app::AppSinkCallbacks::builder()
+            .new_sample(move |appsink| {
+                let sample = appsink
+                    .pull_sample()
+                    .map_err(|_| gst::FlowError::Flushing)?;
+                let (Some(buffer), Some(caps)) = (sample.buffer_owned(), sample.caps()) else {
+                    return Ok(gst::FlowSuccess::Ok);
+                };
+                let video_info = gst_video::VideoInfo::from_caps(caps)
+                    .map_err(|_| gst::FlowError::NotNegotiated)?;
+
+                match gst_video::VideoFrame::from_buffer_readable(buffer, &video_info) {
+                    Ok(frame) => callback(&frame),
+                    Err(_) => log::error!("Could not map frame for the frame callback"),
+                }
+
+                Ok(gst::FlowSuccess::Ok)
+            })
+            .build(),
+    );
+
+    let ghost_pad = gst::GhostPad::with_target(&pad).unwrap();
+    ghost_pad.set_active(true).unwrap();
+    bin.add_pad(&ghost_pad).unwrap();
+
+    Ok(bin.upcast())
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use std::sync::Mutex;
+
+use futures_channel::oneshot;
+use gst::prelude::*;
+use gtk::{gdk, glib};
+
+/// A branch of the viewfinder stream keeping the next frame in memory, as
+/// RGBA.
+#[derive(Debug)]
+pub(crate) struct FrameGrab {
+    bin: gst::Element,
+    receiver: oneshot::Receiver<gst::Sample>,
+}
+
+impl FrameGrab {
+    pub fn new() -> Result<Self, glib::BoolError> {
+        let bin = gst::Bin::new();
+
+        let videoconvert = gst::ElementFactory::make("videoconvert").build()?;
+        let capsfilter = gst::ElementFactory::make("capsfilter")
+            .property(
+                "caps",
+                gst_video::VideoCapsBuilder::new()
+                    .format(gst_video::VideoFormat::Rgba)
+                    .build(),
+            )
+            .build()?;
+        let fakesink = gst::ElementFactory::make("fakesink")
+            .property("async", false)
+            .build()?;
+
+        bin.add_many([&videoconvert, &capsfilter, &fakesink])
+            .unwrap();
+        gst::Element::link_many([&videoconvert, &capsfilter, &fakesink]).unwrap();
+
+        let (sender, receiver) = oneshot::channel();
+        let sender = Mutex::new(Some(sender));
+        fakesink.static_pad("sink").unwrap().add_probe(
+            gst::PadProbeType::BUFFER,
+            move |pad, info| {
+                let Some(gst::PadProbeData::Buffer(ref buffer)) = info.data else {
+                    return gst::PadProbeReturn::Ok;
+                };
+                let Some(caps) = pad.current_caps() else {
+                    return gst::PadProbeReturn::Drop;
+                };
+
+                if let Some(sender) = sender.lock().unwrap().take() {
+                    let sample = gst::Sample::builder().buffer(buffer).caps(&caps).build();
+                    // The receiver is gone if the grab was abandoned.
+                    let _ = sender.send(sample);
+                }
+
+                gst::PadProbeReturn::Drop
+            },
+        );
+
+        let pad = videoconvert.static_pad("sink").unwrap();
+        let ghost_pad = gst::GhostPad::with_target(&pad).unwrap();
+        ghost_pad.set_active(true).unwrap();
+        bin.add_pad(&ghost_pad).unwrap();
+
+        Ok(Self {
+            bin: bin.upcast(),
+            receiver,
+        })
+    }
+
+    pub fn bin(&self) -> &gst::Element {
+        &self.bin
+    }
+
+    /// Waits for the next frame to reach the branch.
+    ///
+    /// # Returns
+    ///
+    /// the frame, or `None` if the branch was destroyed before a frame
+    /// reached it.
+    pub async fn sample(self) -> Option<gst::Sample> {
+        self.receiver.await.ok()
+    }
+}
+
+/// Creates a texture from a sample in a format supported by
+/// [`gdk::MemoryTexture`], such as the ones taken by [`FrameGrab`].
+pub(crate) fn texture_from_sample(sample: &gst::Sample) -> Option<gdk::Texture> {
+    let info = gst_video::VideoInfo::from_caps(sample.caps()?).ok()?;
+    let format = match info.format() {
+        gst_video::VideoFormat::Rgba => gdk::MemoryFormat::R8g8b8a8,
+        gst_video::VideoFormat::Bgra => gdk::MemoryFormat::B8g8r8a8,
+        gst_video::VideoFormat::Rgb => gdk::MemoryFormat::R8g8b8,
+        gst_video::VideoFormat::Bgr => gdk::MemoryFormat::B8g8r8,
+        _ => return None,
+    };
+
+    let buffer = sample.buffer_owned()?.into_mapped_buffer_readable().ok()?;
+    let bytes = glib::Bytes::from_owned(buffer);
+
+    let texture = gdk::MemoryTexture::new(
+        info.width() as i32,
+        info.height() as i32,
+        format,
+        &bytes,
+        info.stride()[0] as usize,
+    );
+
+    Some(texture.upcast())
+}
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use std::sync::{Arc, Mutex};
+
+use gst::prelude::*;
+
+/// Modifies the frames of the camera feed in place.
+///
+/// Processors are added with [`fn@crate::Viewfinder::add_frame_processor`]
+/// and are applied in the order they were added, before frames reach the
+/// viewfinder, pictures and recordings. They are called from a streaming
+/// thread for every frame, and should thus be fast.
+///
+/// Frames are in the raw format negotiated with the camera, see
+/// [`gst_video::VideoFrameRef::format`]. Processors should leave frames in
+/// formats they do not support untouched.
+///
+/// This trait is implemented for closures taking a frame.
+pub trait FrameProcessor: Send + Sync + 'static {
+    /// Modifies `frame` in place.
+    fn process(&self, frame: &mut gst_video::VideoFrameRef<&mut gst::BufferRef>);
+}
+
+impl<F> FrameProcessor for F
+where
+    F: Fn(&mut gst_video::VideoFrameRef<&mut gst::BufferRef>) + Send + Sync + 'static,
+{
+    fn process(&self, frame: &mut gst_video::VideoFrameRef<&mut gst::BufferRef>) {
+        self(frame)
+    }
+}
+
+/// Identifies a processor added to a [`Viewfinder`][crate::Viewfinder].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameProcessorId(u64);
+
+#[derive(Debug, Default)]
+struct State {
+    next_id: u64,
+    processors: Vec<Entry>,
+}
+
+struct Entry {
+    id: FrameProcessorId,
+    processor: Arc<dyn FrameProcessor>,
+    enabled: bool,
+}
+
+impl std::fmt::Debug for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Entry")
+            .field("id", &self.id)
+            .field("enabled", &self.enabled)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The chain of processors applied to the camera feed. It is shared with the
+/// video sources, so that it outlives changes of camera.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct FrameProcessors {
+    state: Arc<Mutex<State>>,
+}
+
+impl FrameProcessors {
+    pub fn add(&self, processor: impl FrameProcessor) -> FrameProcessorId {
+        let mut state = self.state.lock().unwrap();
+
+        let id = FrameProcessorId(state.next_id);
+        state.next_id += 1;
+        state.processors.push(Entry {
+            id,
+            processor: Arc::new(processor),
+            enabled: true,
+        });
+
+        id
+    }
+
+    /// Removes a processor, returns whether it was in the chain.
+    pub fn remove(&self, id: FrameProcessorId) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let len = state.processors.len();
+        state.processors.retain(|entry| entry.id != id);
+
+        state.processors.len() != len
+    }
+
+    /// Enables or disables a processor, returns whether it is in the chain.
+    pub fn set_enabled(&self, id: FrameProcessorId, enabled: bool) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match state.processors.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) => {
+                entry.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether a processor is enabled, or `None` if it is not in the chain.
+    pub fn is_enabled(&self, id: FrameProcessorId) -> Option<bool> {
+        let state = self.state.lock().unwrap();
+        state
+            .processors
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| entry.enabled)
+    }
+
+    fn enabled(&self) -> Vec<Arc<dyn FrameProcessor>> {
+        let state = self.state.lock().unwrap();
+        state
+            .processors
+            .iter()
+            .filter(|entry| entry.enabled)
+            .map(|entry| entry.processor.clone())
+            .collect()
+    }
+
+    /// Applies the enabled processors to the buffers flowing through `pad`.
+    pub fn attach(&self, pad: &gst::Pad) {
+        let processors = self.clone();
+        pad.add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+            // The lock is not held while processing, to not block changes to
+            // the chain.
+            let enabled = processors.enabled();
+            if enabled.is_empty() {
+                return gst::PadProbeReturn::Ok;
+            }
+
+            let Some(gst::PadProbeData::Buffer(ref mut buffer)) = info.data else {
+                return gst::PadProbeReturn::Ok;
+            };
+            let Some(video_info) = pad
+                .current_caps()
+                .and_then(|caps| gst_video::VideoInfo::from_caps(&caps).ok())
+            else {
+                return gst::PadProbeReturn::Ok;
+            };
+
+            let buffer = buffer.make_mut();
+            match gst_video::VideoFrameRef::from_buffer_ref_writable(buffer, &video_info) {
+                Ok(mut frame) => {
+                    for processor in enabled {
+                        processor.process(&mut frame);
+                    }
+                }
+                Err(err) => log::error!("Could not map frame for the frame processors: {err}"),
+            }
+
+            gst::PadProbeReturn::Ok
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_processors() {
+        let processors = FrameProcessors::default();
+
+        let first = processors.add(|_: &mut gst_video::VideoFrameRef<&mut gst::BufferRef>| {});
+        let second = processors.add(|_: &mut gst_video::VideoFrameRef<&mut gst::BufferRef>| {});
+        assert_ne!(first, second);
+        assert_eq!(processors.enabled().len(), 2);
+
+        assert!(processors.set_enabled(first, false));
+        assert_eq!(processors.is_enabled(first), Some(false));
+        assert_eq!(processors.enabled().len(), 1);
+
+        assert!(processors.remove(second));
+        assert!(!processors.remove(second));
+        assert!(!processors.set_enabled(second, true));
+        assert_eq!(processors.is_enabled(second), None);
+        assert!(processors.enabled().is_empty());
+    }
+}
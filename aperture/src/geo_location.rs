@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use gtk::glib;
+
+/// A geographic location, written as geo tags into pictures and videos.
+///
+/// See [`Viewfinder:geo-location`][crate::Viewfinder#geo-location].
+#[derive(Debug, Clone, Copy, PartialEq, glib::Boxed)]
+#[boxed_type(name = "ApertureGeoLocation")]
+pub struct GeoLocation {
+    latitude: f64,
+    longitude: f64,
+    altitude: Option<f64>,
+    accuracy: Option<f64>,
+}
+
+impl GeoLocation {
+    /// Creates a new location from its latitude and longitude, in degrees.
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            altitude: None,
+            accuracy: None,
+        }
+    }
+
+    /// Sets the altitude above sea level, in meters.
+    pub fn with_altitude(mut self, altitude: f64) -> Self {
+        self.altitude = Some(altitude);
+        self
+    }
+
+    /// Sets the horizontal accuracy, in meters.
+    pub fn with_accuracy(mut self, accuracy: f64) -> Self {
+        self.accuracy = Some(accuracy);
+        self
+    }
+
+    /// Gets the latitude, in degrees.
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /// Gets the longitude, in degrees.
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Gets the altitude above sea level, in meters.
+    pub fn altitude(&self) -> Option<f64> {
+        self.altitude
+    }
+
+    /// Gets the horizontal accuracy, in meters.
+    pub fn accuracy(&self) -> Option<f64> {
+        self.accuracy
+    }
+
+    pub(crate) fn add_tags(&self, tagsetter: &gst::TagSetter) {
+        use gst::prelude::*;
+
+        tagsetter
+            .add_tag::<gst::tags::GeoLocationLatitude>(&self.latitude, gst::TagMergeMode::Replace);
+        tagsetter.add_tag::<gst::tags::GeoLocationLongitude>(
+            &self.longitude,
+            gst::TagMergeMode::Replace,
+        );
+        if let Some(altitude) = self.altitude {
+            tagsetter
+                .add_tag::<gst::tags::GeoLocationElevation>(&altitude, gst::TagMergeMode::Replace);
+        }
+        if let Some(accuracy) = self.accuracy {
+            tagsetter.add_tag::<gst::tags::GeoLocationHorizontalError>(
+                &accuracy,
+                gst::TagMergeMode::Replace,
+            );
+        }
+    }
+}
@@ -13,33 +13,74 @@
 //! Aperture needs to initialized before use.
 //! This can be done by calling [`fn@init`] on
 //! [`startup`](fn@gtk::gio::prelude::ApplicationExt::connect_startup).
+//!
+//! # Testing
+//!
+//! Setting the `APERTURE_TEST_CAMERAS` environment variable to a number makes
+//! the [`DeviceProvider`] use synthetic cameras backed by `videotestsrc` and a
+//! microphone backed by `audiotestsrc` instead of real devices, see
+//! [`DeviceProvider::add_test_camera`]. Their controls, see
+//! [`Camera::controls`], are kept in memory.
 
 use std::sync::{Once, OnceLock};
 
 use gst::prelude::*;
 
+mod audio_level;
+mod burst;
 mod camera;
+mod camera_control;
+mod camera_mode;
+mod color_balance;
 mod device_provider;
 mod enums;
 mod error;
+mod frame_callback;
+mod frame_grab;
+mod frame_processor;
+mod geo_location;
+mod microphone;
 mod pipeline_tee;
+mod recording_pause;
+mod test_provider;
+mod timelapse;
 mod utils;
+mod v4l2;
 mod viewfinder;
 
+pub use audio_level::AudioLevel;
+pub(crate) use burst::Burst;
 pub use camera::Camera;
+pub use camera_control::CameraControl;
+pub use camera_mode::CameraMode;
+pub(crate) use color_balance::ColorBalance;
 pub use device_provider::DeviceProvider;
-pub use enums::{CameraLocation, CodeType, ViewfinderState};
-pub use error::{CaptureError, PipewireError, ProviderError};
+pub use enums::{
+    CameraControlId, CameraControlType, CameraLocation, CodeType, PictureFormat,
+    RecordingLimitAction, VideoEffect, VideoFormat, ViewfinderState,
+};
+pub use error::{CaptureError, ControlError, PipewireError, ProviderError};
+pub use frame_callback::Frame;
+pub(crate) use frame_grab::FrameGrab;
+pub(crate) use frame_processor::FrameProcessors;
+pub use frame_processor::{FrameProcessor, FrameProcessorId};
+pub use geo_location::GeoLocation;
+pub use microphone::Microphone;
 pub(crate) use pipeline_tee::PipelineTee;
+pub(crate) use recording_pause::RecordingPause;
+pub(crate) use timelapse::Timelapse;
 pub use viewfinder::Viewfinder;
 
 pub(crate) static APP_ID: OnceLock<&'static str> = OnceLock::new();
 pub(crate) const SUPPORTED_ENCODINGS: [&str; 2] = ["video/x-raw", "image/jpeg"];
 /// The maximum framerate, in frames per second.
 pub(crate) const MAXIMUM_RATE: i32 = 30;
+/// The maximum width and height of a camera mode, in pixels. Unbounded size
+/// ranges are clamped to it.
+pub(crate) const MAXIMUM_DIMENSION: i32 = 16384;
 
 static IS_INIT: Once = Once::new();
-const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Initializes the library
 ///
@@ -61,10 +102,20 @@ pub fn init(app_id: &'static str) {
         Viewfinder::static_type();
         DeviceProvider::static_type();
         Camera::static_type();
+        Microphone::static_type();
+        CameraMode::static_type();
+        AudioLevel::static_type();
+        GeoLocation::static_type();
 
         CodeType::static_type();
         ViewfinderState::static_type();
         CameraLocation::static_type();
+        PictureFormat::static_type();
+        VideoFormat::static_type();
+        RecordingLimitAction::static_type();
+        VideoEffect::static_type();
+        CameraControlId::static_type();
+        CameraControlType::static_type();
     });
 }
 
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use gst::prelude::DeviceExt;
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+mod imp {
+    use std::cell::OnceCell;
+
+    use glib::Properties;
+
+    use super::*;
+
+    #[derive(Debug, Default, Properties)]
+    #[properties(wrapper_type = super::Microphone)]
+    pub struct Microphone {
+        #[property(get, set, construct_only)]
+        device: OnceCell<gst::Device>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Microphone {
+        const NAME: &'static str = "ApertureMicrophone";
+        type Type = super::Microphone;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for Microphone {}
+}
+
+glib::wrapper! {
+    /// A representation of a microphone plugged into a device.
+    ///
+    /// Microphone objects should not be created by a user, and should only be created via a
+    /// [`DeviceProvider`][crate::DeviceProvider].
+    ///
+    /// ## Properties
+    ///
+    ///
+    /// #### `device`
+    ///  The [`gst::Device`][gst::Device] to which this microphone represents.
+    ///
+    /// Readable | Writeable
+    ///
+    /// # Implements
+    ///
+    /// [`glib::ObjectExt`][trait@gtk::glib::ObjectExt]
+    pub struct Microphone(ObjectSubclass<imp::Microphone>);
+}
+
+impl Microphone {
+    /// Gets the display name of the microphone represented by `self`.
+    ///
+    /// # Returns
+    ///
+    /// the display name.
+    pub fn display_name(&self) -> glib::GString {
+        self.device().display_name()
+    }
+
+    /// Gets the user-set nickname of the microphone represented by `self`.
+    ///
+    /// # Returns
+    ///
+    /// the display name if set.
+    pub fn nick(&self) -> Option<String> {
+        self.device().properties().and_then(|properties| {
+            properties
+                .value("node.nick")
+                .ok()
+                .and_then(|value| value.get::<String>().ok())
+        })
+    }
+
+    /// Gets the `serial` of the device, see [`Camera`][crate::Camera].
+    pub(crate) fn target_object(&self) -> Option<u64> {
+        let device = self.device();
+        if device.has_property("serial", Some(u64::static_type())) {
+            Some(device.property::<u64>("serial"))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn new(device: &gst::Device) -> Self {
+        glib::Object::builder().property("device", device).build()
+    }
+
+    pub(crate) fn create_element(&self) -> Result<gst::Element, glib::BoolError> {
+        let element = self.device().create_element(None)?;
+        if element.has_property("client-name", Some(String::static_type())) {
+            element.set_property("client-name", crate::APP_ID.get().unwrap());
+        }
+        Ok(element)
+    }
+}
@@ -5,6 +5,9 @@ use gst::prelude::*;
 use gst::subclass::prelude::*;
 use gtk::glib;
 
+/// The number of frames queued by leaky branches.
+const LEAKY_QUEUE_SIZE: u32 = 2;
+
 mod imp {
     use std::sync::{Mutex, OnceLock};
 
@@ -64,9 +67,25 @@ impl PipelineTee {
     }
 
     pub fn add_branch(&self, branch: &gst::Element) {
-        let imp = self.imp();
-
         let queue = gst::ElementFactory::make("queue").build().unwrap();
+        self.link_branch(branch, queue);
+    }
+
+    /// Adds a branch which must never block the other ones. Frames are dropped
+    /// instead of queued while the branch is busy.
+    pub fn add_leaky_branch(&self, branch: &gst::Element) {
+        let queue = gst::ElementFactory::make("queue")
+            .property_from_str("leaky", "downstream")
+            .property("max-size-buffers", LEAKY_QUEUE_SIZE)
+            .property("max-size-bytes", 0u32)
+            .property("max-size-time", 0u64)
+            .build()
+            .unwrap();
+        self.link_branch(branch, queue);
+    }
+
+    fn link_branch(&self, branch: &gst::Element, queue: gst::Element) {
+        let imp = self.imp();
 
         imp.hashmap
             .lock()
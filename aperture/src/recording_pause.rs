@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use std::cell::{Cell, RefCell};
+use std::sync::{Arc, Mutex};
+
+use gst::prelude::*;
+
+#[derive(Debug, Default)]
+struct State {
+    is_paused: bool,
+    paused_at: Option<gst::ClockTime>,
+    // The total time spent paused.
+    offset: gst::ClockTime,
+}
+
+/// Pauses a recording by dropping the buffers flowing through the recorded
+/// pads, and shifting the timestamps of the following ones by the time spent
+/// paused, so that the recording is a single continuous file.
+#[derive(Debug, Default)]
+pub(crate) struct RecordingPause {
+    state: Arc<Mutex<State>>,
+    probes: RefCell<Vec<(gst::Pad, gst::PadProbeId)>>,
+    // Whether the timestamps are shifted when resuming.
+    shift: Cell<bool>,
+}
+
+impl RecordingPause {
+    /// Starts handling the buffers flowing through `pads`. Unless `shift` is
+    /// set, the timestamps are left untouched when resuming, for streams that
+    /// are timestamped otherwise, e.g. time-lapses.
+    pub fn attach(&self, pads: impl IntoIterator<Item = gst::Pad>, shift: bool) {
+        self.detach();
+        self.shift.set(shift);
+
+        let mut probes = self.probes.borrow_mut();
+        for pad in pads {
+            let state = self.state.clone();
+            let probe = pad.add_probe(
+                gst::PadProbeType::BUFFER | gst::PadProbeType::BUFFER_LIST,
+                move |_, _| {
+                    if state.lock().unwrap().is_paused {
+                        gst::PadProbeReturn::Drop
+                    } else {
+                        gst::PadProbeReturn::Ok
+                    }
+                },
+            );
+            if let Some(probe) = probe {
+                probes.push((pad, probe));
+            }
+        }
+    }
+
+    /// Stops handling buffers, this resets the pause.
+    pub fn detach(&self) {
+        for (pad, probe) in self.probes.take() {
+            pad.remove_probe(probe);
+            pad.set_offset(0);
+        }
+
+        *self.state.lock().unwrap() = State::default();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().unwrap().is_paused
+    }
+
+    /// The time spent paused up to `running_time`, the running time of the
+    /// pipeline.
+    pub fn paused_duration(&self, running_time: Option<gst::ClockTime>) -> gst::ClockTime {
+        let state = self.state.lock().unwrap();
+        let current = running_time
+            .zip(state.paused_at)
+            .and_then(|(now, paused_at)| now.checked_sub(paused_at))
+            .unwrap_or_default();
+
+        state.offset + current
+    }
+
+    /// Pauses at `running_time`, the running time of the pipeline.
+    pub fn pause(&self, running_time: Option<gst::ClockTime>) {
+        let mut state = self.state.lock().unwrap();
+        state.is_paused = true;
+        state.paused_at = running_time;
+    }
+
+    /// Resumes at `running_time`, the running time of the pipeline.
+    pub fn resume(&self, running_time: Option<gst::ClockTime>) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(paused) = running_time
+            .zip(state.paused_at)
+            .and_then(|(now, paused_at)| now.checked_sub(paused_at))
+        {
+            state.offset += paused;
+        }
+        state.is_paused = false;
+        state.paused_at = None;
+
+        if !self.shift.get() {
+            return;
+        }
+        let offset = -(state.offset.nseconds() as i64);
+        for (pad, _) in self.probes.borrow().iter() {
+            pad.set_offset(offset);
+        }
+    }
+}
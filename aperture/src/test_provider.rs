@@ -0,0 +1,533 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use gst::prelude::*;
+use gst::subclass::prelude::*;
+use gtk::glib;
+use once_cell::sync::Lazy;
+
+/// Environment variable selecting the test backend, its value is the number of
+/// synthetic cameras available at startup.
+pub(crate) const TEST_CAMERAS_ENV: &str = "APERTURE_TEST_CAMERAS";
+/// Environment variable overriding the caps of the synthetic cameras.
+pub(crate) const TEST_CAMERA_CAPS_ENV: &str = "APERTURE_TEST_CAMERA_CAPS";
+/// Environment variable pointing to an image to be used as the feed of the
+/// synthetic cameras, e.g. a QR code.
+pub(crate) const TEST_CAMERA_IMAGE_ENV: &str = "APERTURE_TEST_CAMERA_IMAGE";
+
+const PATTERNS: [&str; 4] = ["smpte", "ball", "pinwheel", "gradient"];
+
+static CAT: Lazy<gst::DebugCategory> = Lazy::new(|| {
+    gst::DebugCategory::new(
+        "aperturetest",
+        gst::DebugColorFlags::empty(),
+        Some("Aperture test backend"),
+    )
+});
+
+mod imp {
+    use std::sync::{Mutex, OnceLock};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct TestDevice {
+        pub pattern: OnceLock<&'static str>,
+        pub image: OnceLock<Option<PathBuf>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestDevice {
+        const NAME: &'static str = "ApertureTestDevice";
+        type Type = super::TestDevice;
+        type ParentType = gst::Device;
+    }
+
+    impl ObjectImpl for TestDevice {}
+    impl GstObjectImpl for TestDevice {}
+
+    impl DeviceImpl for TestDevice {
+        fn create_element(&self, name: Option<&str>) -> Result<gst::Element, gst::LoggableError> {
+            let obj = self.obj();
+
+            let element = if obj.device_class() == "Audio/Source" {
+                gst::ElementFactory::make("audiotestsrc")
+                    .property("is-live", true)
+                    .build()?
+            } else if let Some(image) = self.image.get().and_then(|image| image.as_deref()) {
+                create_image_bin(image)?
+            } else {
+                gst::ElementFactory::make("videotestsrc")
+                    .property("is-live", true)
+                    .property_from_str("pattern", self.pattern.get().unwrap())
+                    .build()?
+            };
+
+            if let Some(name) = name {
+                element.set_property("name", name);
+            }
+
+            Ok(element)
+        }
+
+        fn reconfigure_element(&self, element: &gst::Element) -> Result<(), gst::LoggableError> {
+            let is_videotestsrc = element
+                .factory()
+                .is_some_and(|factory| factory.name() == "videotestsrc");
+
+            if is_videotestsrc && self.image.get().is_some_and(|image| image.is_none()) {
+                element.set_property_from_str("pattern", self.pattern.get().unwrap());
+                Ok(())
+            } else {
+                Err(gst::loggable_error!(CAT, "Cannot reconfigure {element:?}"))
+            }
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct TestDeviceProvider {
+        pub devices: Mutex<Vec<gst::Device>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestDeviceProvider {
+        const NAME: &'static str = "ApertureTestDeviceProvider";
+        type Type = super::TestDeviceProvider;
+        type ParentType = gst::DeviceProvider;
+    }
+
+    impl ObjectImpl for TestDeviceProvider {}
+    impl GstObjectImpl for TestDeviceProvider {}
+
+    impl DeviceProviderImpl for TestDeviceProvider {
+        fn metadata() -> Option<&'static gst::subclass::DeviceProviderMetadata> {
+            static METADATA: Lazy<gst::subclass::DeviceProviderMetadata> = Lazy::new(|| {
+                gst::subclass::DeviceProviderMetadata::new(
+                    "Aperture Test Device Provider",
+                    "Source/Video",
+                    "Provides synthetic cameras and microphones for testing",
+                    "The GNOME Project",
+                )
+            });
+
+            Some(&*METADATA)
+        }
+
+        // The default implementation of `start` adds the probed devices.
+        fn probe(&self) -> Vec<gst::Device> {
+            self.devices.lock().unwrap().clone()
+        }
+    }
+}
+
+glib::wrapper! {
+    pub(crate) struct TestDevice(ObjectSubclass<imp::TestDevice>)
+        @extends gst::Device, gst::Object;
+}
+
+impl TestDevice {
+    fn new_camera(
+        display_name: &str,
+        caps: &gst::Caps,
+        index: usize,
+        image: Option<&Path>,
+    ) -> Self {
+        let properties = gst::Structure::builder("properties")
+            .field("device.api", "aperture-test")
+            .field("node.nick", display_name)
+            .build();
+
+        let device: Self = glib::Object::builder()
+            .property("display-name", display_name)
+            .property("caps", caps)
+            .property("device-class", "Video/Source")
+            .property("properties", properties)
+            .build();
+
+        let imp = device.imp();
+        imp.pattern.set(PATTERNS[index % PATTERNS.len()]).unwrap();
+        imp.image.set(image.map(Path::to_path_buf)).unwrap();
+
+        device
+    }
+
+    fn new_microphone(display_name: &str) -> Self {
+        let caps = gst::Caps::builder("audio/x-raw").build();
+        let properties = gst::Structure::builder("properties")
+            .field("device.api", "aperture-test")
+            .build();
+
+        glib::Object::builder()
+            .property("display-name", display_name)
+            .property("caps", caps)
+            .property("device-class", "Audio/Source")
+            .property("properties", properties)
+            .build()
+    }
+}
+
+glib::wrapper! {
+    pub(crate) struct TestDeviceProvider(ObjectSubclass<imp::TestDeviceProvider>)
+        @extends gst::DeviceProvider, gst::Object;
+}
+
+impl TestDeviceProvider {
+    /// Creates a provider with `n_cameras` synthetic cameras and a single
+    /// synthetic microphone.
+    pub fn new(n_cameras: u32) -> Self {
+        let provider: Self = glib::Object::builder()
+            .property("name", "aperturetestdeviceprovider")
+            .build();
+
+        let caps = caps_from_env();
+        let image = image_from_env();
+
+        let mut devices = (0..n_cameras as usize)
+            .map(|index| {
+                let display_name = format!("Test Camera {}", index + 1);
+                TestDevice::new_camera(&display_name, &caps, index, image.as_deref()).upcast()
+            })
+            .collect::<Vec<gst::Device>>();
+        devices.push(TestDevice::new_microphone("Test Microphone").upcast());
+
+        provider.imp().devices.lock().unwrap().extend(devices);
+
+        provider
+    }
+
+    /// Adds a synthetic camera. If the provider is started this will be
+    /// announced as a hotplugged device.
+    pub fn add_camera(&self, display_name: &str, caps: Option<&gst::Caps>, image: Option<&Path>) {
+        let mut devices = self.imp().devices.lock().unwrap();
+
+        let caps = caps.cloned().unwrap_or_else(caps_from_env);
+        let image = image.map(Path::to_path_buf).or_else(image_from_env);
+        let device: gst::Device =
+            TestDevice::new_camera(display_name, &caps, devices.len(), image.as_deref()).upcast();
+        devices.push(device.clone());
+        drop(devices);
+
+        if self.is_started() {
+            self.device_add(&device);
+        }
+    }
+
+    /// Removes a synthetic camera. If the provider is started this will be
+    /// announced as an unplugged device.
+    pub fn remove_device(&self, device: &gst::Device) {
+        let mut devices = self.imp().devices.lock().unwrap();
+        devices.retain(|d| d != device);
+        drop(devices);
+
+        if self.is_started() {
+            self.device_remove(device);
+        }
+    }
+}
+
+/// Returns the number of cameras requested via [`TEST_CAMERAS_ENV`], if set.
+pub(crate) fn cameras_from_env() -> Option<u32> {
+    let value = std::env::var(TEST_CAMERAS_ENV).ok()?;
+    match value.parse::<u32>() {
+        Ok(n_cameras) => Some(n_cameras),
+        Err(err) => {
+            log::warn!("Invalid value {value:?} for {TEST_CAMERAS_ENV}, using one camera: {err}");
+            Some(1)
+        }
+    }
+}
+
+fn caps_from_env() -> gst::Caps {
+    std::env::var(TEST_CAMERA_CAPS_ENV)
+        .ok()
+        .and_then(|caps| {
+            gst::Caps::from_str(&caps)
+                .inspect_err(|err| log::warn!("Invalid caps {caps:?}: {err}"))
+                .ok()
+        })
+        .unwrap_or_else(default_caps)
+}
+
+fn image_from_env() -> Option<PathBuf> {
+    std::env::var_os(TEST_CAMERA_IMAGE_ENV).map(PathBuf::from)
+}
+
+fn default_caps() -> gst::Caps {
+    [(1920, 1080), (1280, 720), (640, 480)]
+        .into_iter()
+        .map(|(width, height)| {
+            gst_video::VideoCapsBuilder::new()
+                .format(gst_video::VideoFormat::I420)
+                .width(width)
+                .height(height)
+                .framerate(gst::Fraction::new(30, 1))
+                .build()
+        })
+        .collect()
+}
+
+fn create_image_bin(image: &Path) -> Result<gst::Element, glib::BoolError> {
+    let bin = gst::Bin::new();
+
+    let filesrc = gst::ElementFactory::make("filesrc")
+        .property("location", image.display().to_string())
+        .build()?;
+    let decodebin = gst::ElementFactory::make("decodebin").build()?;
+    let imagefreeze = gst::ElementFactory::make("imagefreeze")
+        .property("is-live", true)
+        .build()?;
+    let videoconvert = gst::ElementFactory::make("videoconvert").build()?;
+    let videoscale = gst::ElementFactory::make("videoscale").build()?;
+
+    bin.add_many([
+        &filesrc,
+        &decodebin,
+        &imagefreeze,
+        &videoconvert,
+        &videoscale,
+    ])?;
+    filesrc.link(&decodebin)?;
+    gst::Element::link_many([&imagefreeze, &videoconvert, &videoscale])?;
+
+    decodebin.connect_pad_added(glib::clone!(@weak imagefreeze => move |_, pad| {
+        let sink_pad = imagefreeze.static_pad("sink").unwrap();
+        if !sink_pad.is_linked() {
+            if let Err(err) = pad.link(&sink_pad) {
+                log::error!("Could not link decodebin to imagefreeze: {err}");
+            }
+        }
+    }));
+
+    let ghost_pad = gst::GhostPad::with_target(&videoscale.static_pad("src").unwrap())?;
+    ghost_pad.set_active(true)?;
+    bin.add_pad(&ghost_pad)?;
+
+    Ok(bin.upcast())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    use futures_channel::oneshot;
+    use gtk::gdk;
+    use gtk::prelude::*;
+
+    use super::*;
+
+    /// An EAN-13 barcode encoding [`BARCODE`].
+    const BARCODE_IMAGE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/ean13.png");
+    const BARCODE: &str = "4006381333931";
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Starts the device provider with the test backend, whose cameras show
+    /// [`BARCODE_IMAGE`].
+    fn start_provider() -> &'static crate::DeviceProvider {
+        std::env::set_var(TEST_CAMERAS_ENV, "1");
+        std::env::set_var(TEST_CAMERA_IMAGE_ENV, BARCODE_IMAGE);
+        crate::init("org.gnome.Aperture.Test");
+
+        let provider = crate::DeviceProvider::instance();
+        provider.start().unwrap();
+
+        provider
+    }
+
+    /// Creates a viewfinder in a window, so that its stream is started, and
+    /// waits for its first frame.
+    async fn start_viewfinder() -> (gtk::Window, crate::Viewfinder) {
+        start_provider();
+
+        let viewfinder = crate::Viewfinder::new();
+        let paintable = viewfinder
+            .imp()
+            .sink_paintable
+            .get()
+            .unwrap()
+            .property::<gdk::Paintable>("paintable");
+        let (frame, receiver) = signal_channel();
+        paintable.connect_invalidate_contents(move |_| frame(()));
+
+        let window = gtk::Window::builder().child(&viewfinder).build();
+        window.present();
+        receive(receiver, "frame").await;
+
+        // The first frame can be shown before the pipeline is playing.
+        if viewfinder.state() != crate::ViewfinderState::Ready {
+            let (ready, receiver) = signal_channel();
+            viewfinder.connect_state_notify(move |viewfinder| {
+                if viewfinder.state() == crate::ViewfinderState::Ready {
+                    ready(());
+                }
+            });
+            receive(receiver, "ready state").await;
+        }
+
+        (window, viewfinder)
+    }
+
+    /// Creates a callback to be passed to a signal handler and a receiver for
+    /// the value of its first call.
+    fn signal_channel<T: 'static>() -> (impl Fn(T) + 'static, oneshot::Receiver<T>) {
+        let (sender, receiver) = oneshot::channel();
+        let sender = Cell::new(Some(sender));
+        let callback = move |value| {
+            if let Some(sender) = sender.take() {
+                let _ = sender.send(value);
+            }
+        };
+
+        (callback, receiver)
+    }
+
+    async fn receive<T>(receiver: oneshot::Receiver<T>, what: &str) -> T {
+        glib::future_with_timeout(TIMEOUT, receiver)
+            .await
+            .unwrap_or_else(|_| panic!("No {what} in {TIMEOUT:?}"))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_probe() {
+        gst::init().expect("Failed to initalize gst");
+
+        let provider = TestDeviceProvider::new(2);
+        let devices = provider.devices();
+
+        let cameras = devices
+            .iter()
+            .filter(|device| device.device_class() == "Video/Source")
+            .count();
+        let microphones = devices
+            .iter()
+            .filter(|device| device.device_class() == "Audio/Source")
+            .count();
+
+        assert_eq!(cameras, 2);
+        assert_eq!(microphones, 1);
+
+        for device in devices {
+            assert!(device.create_element(None).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_camera_controls() {
+        use crate::{CameraControlId, ControlError};
+
+        gst::init().expect("Failed to initalize gst");
+
+        let provider = TestDeviceProvider::new(1);
+        let device = provider
+            .devices()
+            .into_iter()
+            .find(|device| device.device_class() == "Video/Source")
+            .unwrap();
+        let camera = crate::Camera::new(&device);
+
+        assert_eq!(camera.controls().len(), CameraControlId::ALL.len());
+
+        let exposure = camera.control(CameraControlId::ExposureTime).unwrap();
+        assert!(exposure.is_inactive());
+
+        camera
+            .set_control(CameraControlId::AutoExposure, 1)
+            .unwrap();
+        camera
+            .set_control(CameraControlId::ExposureTime, 300)
+            .unwrap();
+        let exposure = camera.control(CameraControlId::ExposureTime).unwrap();
+        assert!(!exposure.is_inactive());
+        assert_eq!(exposure.value(), 300);
+
+        assert_eq!(
+            camera.set_control(CameraControlId::ExposureTime, 0),
+            Err(ControlError::OutOfRange)
+        );
+        assert_eq!(
+            camera.set_control(CameraControlId::AutoExposure, 2),
+            Err(ControlError::OutOfRange)
+        );
+    }
+
+    #[gtk::test]
+    async fn test_take_picture() {
+        let (window, viewfinder) = start_viewfinder().await;
+        let dir = glib::dir_make_tmp(Some("aperture-test-XXXXXX")).unwrap();
+
+        let (done, receiver) = signal_channel();
+        viewfinder.connect_picture_done(move |_, file, _| done(file.and_then(|file| file.path())));
+        viewfinder.take_picture(dir.join("picture.jpeg")).unwrap();
+
+        let path = receive(receiver, "picture")
+            .await
+            .expect("No picture was saved");
+        assert!(path.metadata().unwrap().len() > 0);
+
+        window.destroy();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[gtk::test]
+    async fn test_record_video() {
+        let (window, viewfinder) = start_viewfinder().await;
+        let dir = glib::dir_make_tmp(Some("aperture-test-XXXXXX")).unwrap();
+
+        let (done, receiver) = signal_channel();
+        viewfinder.connect_recording_done(move |_, file| done(file.and_then(|file| file.path())));
+        viewfinder
+            .start_recording(dir.join("recording.webm"))
+            .unwrap();
+        glib::timeout_future(Duration::from_secs(1)).await;
+        viewfinder.stop_recording().unwrap();
+
+        let path = receive(receiver, "recording")
+            .await
+            .expect("No recording was saved");
+        assert!(path.metadata().unwrap().len() > 0);
+
+        window.destroy();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[gtk::test]
+    async fn test_hotplug() {
+        let provider = start_provider();
+        let n_cameras = provider.n_items();
+
+        let (added, added_receiver) = signal_channel();
+        provider.connect_camera_added(move |_, camera| added(camera.clone()));
+        let (removed, removed_receiver) = signal_channel();
+        provider.connect_camera_removed(move |_, camera| removed(camera.clone()));
+
+        provider
+            .add_test_camera("Hotplugged Camera", None, None)
+            .unwrap();
+        let camera = receive(added_receiver, "camera-added").await;
+        assert_eq!(camera.display_name(), "Hotplugged Camera");
+        assert_eq!(provider.n_items(), n_cameras + 1);
+
+        provider.remove_test_camera(&camera).unwrap();
+        assert_eq!(receive(removed_receiver, "camera-removed").await, camera);
+        assert_eq!(provider.n_items(), n_cameras);
+    }
+
+    #[gtk::test]
+    async fn test_detect_codes() {
+        let (window, viewfinder) = start_viewfinder().await;
+
+        let (detected, receiver) = signal_channel();
+        viewfinder.connect_code_detected(move |_, code_type, data| {
+            detected((code_type, data.to_owned()))
+        });
+        viewfinder.set_detect_codes(true);
+
+        let (code_type, data) = receive(receiver, "code-detected").await;
+        assert_eq!(code_type, crate::CodeType::Ean13);
+        assert_eq!(data, BARCODE);
+
+        window.destroy();
+    }
+}
@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+use gst::prelude::*;
+
+#[derive(Debug, Default)]
+struct State {
+    // The timestamp of the first kept buffer.
+    first_pts: Option<gst::ClockTime>,
+    // The timestamp from which the next buffer is kept.
+    next_pts: Option<gst::ClockTime>,
+    frames: u64,
+}
+
+/// Turns a video stream into a time-lapse, by keeping one buffer every
+/// interval and timestamping the kept buffers so that they play at a normal
+/// frame rate.
+#[derive(Debug, Default)]
+pub(crate) struct Timelapse {
+    probe: RefCell<Option<(gst::Pad, gst::PadProbeId)>>,
+}
+
+impl Timelapse {
+    /// Keeps one buffer flowing through `pad` every `interval`, to be played
+    /// at `framerate` frames per second.
+    pub fn attach(&self, pad: gst::Pad, interval: gst::ClockTime, framerate: u32) {
+        self.detach();
+
+        let frame_duration = gst::ClockTime::from_nseconds(
+            gst::ClockTime::SECOND.nseconds() / u64::from(framerate.max(1)),
+        );
+        let state = Mutex::new(State::default());
+        let probe = pad.add_probe(gst::PadProbeType::BUFFER, move |_, info| {
+            let Some(gst::PadProbeData::Buffer(ref mut buffer)) = info.data else {
+                return gst::PadProbeReturn::Ok;
+            };
+            let Some(pts) = buffer.pts() else {
+                return gst::PadProbeReturn::Drop;
+            };
+
+            let mut state = state.lock().unwrap();
+            if state.next_pts.is_some_and(|next_pts| pts < next_pts) {
+                return gst::PadProbeReturn::Drop;
+            }
+            // The stream starts at the first buffer, later ones are placed
+            // one frame after another.
+            let first_pts = *state.first_pts.get_or_insert(pts);
+            state.next_pts = Some(pts + interval);
+
+            let buffer = buffer.make_mut();
+            buffer.set_pts(
+                first_pts + gst::ClockTime::from_nseconds(frame_duration.nseconds() * state.frames),
+            );
+            buffer.set_dts(gst::ClockTime::NONE);
+            buffer.set_duration(frame_duration);
+            state.frames += 1;
+
+            gst::PadProbeReturn::Ok
+        });
+
+        if let Some(probe) = probe {
+            self.probe.replace(Some((pad, probe)));
+        }
+    }
+
+    /// Stops handling buffers.
+    pub fn detach(&self) {
+        if let Some((pad, probe)) = self.probe.take() {
+            pad.remove_probe(probe);
+        }
+    }
+}
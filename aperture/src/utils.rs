@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 pub(crate) mod caps {
     use once_cell::sync::Lazy;
 
@@ -48,6 +50,131 @@ pub(crate) mod caps {
             })
     }
 
+    /// Common framerates, used to expand framerate ranges.
+    const COMMON_FRAMERATES: [i32; 11] = [240, 120, 60, 50, 30, 25, 24, 20, 15, 10, 5];
+
+    /// Expands `caps` into the list of modes they describe, sorted from
+    /// highest to lowest resolution. Structures with an unsupported encoding
+    /// are skipped.
+    pub(crate) fn modes(caps: &gst::Caps) -> Vec<crate::CameraMode> {
+        let mut modes: Vec<crate::CameraMode> = Vec::new();
+        for structure in caps.iter() {
+            let encoding = structure.name().as_str();
+            if !crate::SUPPORTED_ENCODINGS.contains(&encoding) {
+                continue;
+            }
+
+            let framerates = fractions(structure, "framerate");
+            for format in formats(structure) {
+                for width in ints(structure, "width") {
+                    for height in ints(structure, "height") {
+                        let mode = crate::CameraMode::new(
+                            encoding,
+                            format,
+                            width,
+                            height,
+                            framerates.clone(),
+                        );
+                        if let Some(existing) = modes.iter_mut().find(|m| m.same_size(&mode)) {
+                            existing.add_framerates(mode.framerates());
+                        } else {
+                            modes.push(mode);
+                        }
+                    }
+                }
+            }
+        }
+
+        modes.sort_by_key(|mode| {
+            std::cmp::Reverse(i64::from(mode.width()) * i64::from(mode.height()))
+        });
+
+        modes
+    }
+
+    /// Gets the values of an integer field, which can be a single value, a
+    /// list or an array. Ranges are represented by their maximum, at most
+    /// `crate::MAXIMUM_DIMENSION`.
+    pub(crate) fn ints(structure: &gst::StructureRef, field: &str) -> Vec<i32> {
+        let Ok(value) = structure.value(field) else {
+            return Vec::new();
+        };
+
+        if let Ok(int) = value.get::<i32>() {
+            vec![int]
+        } else if let Ok(range) = value.get::<gst::IntRange<i32>>() {
+            vec![range.max().min(crate::MAXIMUM_DIMENSION).max(range.min())]
+        } else if let Ok(list) = value.get::<gst::List>() {
+            list.iter().filter_map(|v| v.get::<i32>().ok()).collect()
+        } else if let Ok(array) = value.get::<gst::Array>() {
+            array.iter().filter_map(|v| v.get::<i32>().ok()).collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Gets the values of a fraction field, sorted from highest to lowest.
+    /// Ranges are expanded into their bounds and the common framerates
+    /// between them.
+    pub(crate) fn fractions(structure: &gst::StructureRef, field: &str) -> Vec<gst::Fraction> {
+        let Ok(value) = structure.value(field) else {
+            return Vec::new();
+        };
+
+        let mut fractions = if let Ok(fraction) = value.get::<gst::Fraction>() {
+            vec![fraction]
+        } else if let Ok(range) = value.get::<gst::FractionRange>() {
+            let (min, max) = (range.min(), range.max());
+            COMMON_FRAMERATES
+                .into_iter()
+                .map(|rate| gst::Fraction::new(rate, 1))
+                .filter(|rate| *rate > min && *rate < max)
+                .chain([min, max])
+                .collect()
+        } else if let Ok(list) = value.get::<gst::List>() {
+            list.iter()
+                .filter_map(|v| v.get::<gst::Fraction>().ok())
+                .collect()
+        } else if let Ok(array) = value.get::<gst::Array>() {
+            array
+                .iter()
+                .filter_map(|v| v.get::<gst::Fraction>().ok())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // A framerate of 0/1 means variable framerate and is not useful.
+        fractions.retain(|fraction| fraction.numer() > 0);
+        fractions.sort_by(|a, b| b.cmp(a));
+        fractions.dedup();
+
+        fractions
+    }
+
+    /// Gets the pixel formats of `structure`, or a single `None` if it has
+    /// none, e.g. for `image/jpeg`.
+    pub(crate) fn formats(structure: &gst::StructureRef) -> Vec<Option<gst_video::VideoFormat>> {
+        let Ok(value) = structure.value("format") else {
+            return vec![None];
+        };
+
+        let names: Vec<&str> = if let Ok(name) = value.get::<&str>() {
+            vec![name]
+        } else if let Ok(list) = value.get::<gst::List>() {
+            list.iter().filter_map(|v| v.get::<&str>().ok()).collect()
+        } else {
+            Vec::new()
+        };
+
+        names
+            .into_iter()
+            .map(gst_video::VideoFormat::from_string)
+            .filter(|format| *format != gst_video::VideoFormat::Unknown)
+            .map(Some)
+            .collect()
+    }
+
     pub(crate) fn best_resolution_for_fps(caps: &gst::Caps, framerate: gst::Fraction) -> gst::Caps {
         // There are multiple aspect rations for 1080p. Therefore, we look for
         // height rather than width.
@@ -83,10 +210,43 @@ pub(crate) mod caps {
     }
 }
 
+/// The location of the `segment`-th file of a recording started at
+/// `location`, e.g. `video-2.webm` for `video.webm`. The first segment is
+/// saved at `location` itself.
+pub(crate) fn segment_location(location: &Path, segment: u32) -> PathBuf {
+    if segment <= 1 {
+        return location.to_owned();
+    }
+
+    let stem = location.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match location.extension() {
+        Some(extension) => format!("{stem}-{segment}.{}", extension.to_string_lossy()),
+        None => format!("{stem}-{segment}"),
+    };
+
+    location.with_file_name(name)
+}
+
 #[cfg(test)]
 mod tests {
+    use gst::prelude::*;
+
     use super::*;
 
+    #[test]
+    fn test_segment_location() {
+        let location = Path::new("/videos/Recording.webm");
+        assert_eq!(segment_location(location, 1), location);
+        assert_eq!(
+            segment_location(location, 2),
+            Path::new("/videos/Recording-2.webm")
+        );
+        assert_eq!(
+            segment_location(Path::new("/videos/Recording"), 12),
+            Path::new("/videos/Recording-12")
+        );
+    }
+
     #[test]
     fn test_is_infrared() {
         gst::init().expect("Failed to initalize gst");
@@ -169,6 +329,121 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fractions() {
+        gst::init().expect("Failed to initalize gst");
+
+        let fixed = gst::Structure::builder("video/x-raw")
+            .field("framerate", gst::Fraction::new(30, 1))
+            .build();
+        assert_eq!(
+            caps::fractions(&fixed, "framerate"),
+            vec![gst::Fraction::new(30, 1)]
+        );
+
+        let list = gst::Structure::builder("video/x-raw")
+            .field(
+                "framerate",
+                gst::List::from_values(
+                    [gst::Fraction::new(15, 1), gst::Fraction::new(30, 1)]
+                        .iter()
+                        .map(|f| f.to_send_value()),
+                ),
+            )
+            .build();
+        assert_eq!(
+            caps::fractions(&list, "framerate"),
+            vec![gst::Fraction::new(30, 1), gst::Fraction::new(15, 1)]
+        );
+
+        let range = gst::Structure::builder("video/x-raw")
+            .field(
+                "framerate",
+                gst::FractionRange::new(gst::Fraction::new(0, 1), gst::Fraction::new(30, 1)),
+            )
+            .build();
+        assert_eq!(
+            caps::fractions(&range, "framerate"),
+            [30, 25, 24, 20, 15, 10, 5]
+                .into_iter()
+                .map(|rate| gst::Fraction::new(rate, 1))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_modes() {
+        gst::init().expect("Failed to initalize gst");
+
+        let caps = [
+            gst_video::VideoCapsBuilder::new()
+                .format_list([gst_video::VideoFormat::Yuy2, gst_video::VideoFormat::Nv12])
+                .width(640)
+                .height(480)
+                .framerate(gst::Fraction::new(30, 1))
+                .build(),
+            gst_video::VideoCapsBuilder::new()
+                .format(gst_video::VideoFormat::Yuy2)
+                .width(640)
+                .height(480)
+                .framerate(gst::Fraction::new(15, 1))
+                .build(),
+            gst_video::VideoCapsBuilder::for_encoding("image/jpeg")
+                .width(1920)
+                .height(1080)
+                .framerate(gst::Fraction::new(30, 1))
+                .build(),
+            gst_video::VideoCapsBuilder::for_encoding("video/x-h264")
+                .width(1920)
+                .height(1080)
+                .build(),
+        ]
+        .into_iter()
+        .collect::<gst::Caps>();
+
+        let modes = caps::modes(&caps);
+        assert_eq!(modes.len(), 3);
+
+        assert_eq!(modes[0].encoding(), "image/jpeg");
+        assert_eq!(modes[0].format(), None);
+        assert_eq!((modes[0].width(), modes[0].height()), (1920, 1080));
+
+        let yuy2 = modes
+            .iter()
+            .find(|mode| mode.format() == Some(gst_video::VideoFormat::Yuy2))
+            .unwrap();
+        assert_eq!(
+            yuy2.framerates(),
+            [gst::Fraction::new(30, 1), gst::Fraction::new(15, 1)]
+        );
+        assert!(!yuy2.caps().is_fixed());
+        assert!(yuy2
+            .caps_with_framerate(gst::Fraction::new(15, 1))
+            .is_subset(&caps));
+
+        // Unbounded ranges are clamped to a sensible size.
+        let caps = [
+            gst_video::VideoCapsBuilder::new()
+                .format(gst_video::VideoFormat::Yuy2)
+                .width(640)
+                .height(480)
+                .build(),
+            gst_video::VideoCapsBuilder::new()
+                .format(gst_video::VideoFormat::Nv12)
+                .width_range(1..)
+                .height_range(1..)
+                .build(),
+        ]
+        .into_iter()
+        .collect::<gst::Caps>();
+
+        let modes = caps::modes(&caps);
+        assert_eq!(modes.len(), 2);
+        assert_eq!(modes[0].width(), crate::MAXIMUM_DIMENSION);
+        assert_eq!(modes[0].height(), crate::MAXIMUM_DIMENSION);
+        assert_eq!(modes[1].width(), 640);
+    }
+
     #[test]
     fn test_best_height() {
         gst::init().expect("Failed to initalize gst");
@@ -0,0 +1,253 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use std::fs::File;
+use std::os::fd::AsRawFd;
+use std::path::PathBuf;
+
+use crate::{CameraControl, CameraControlId, CameraControlType, ControlError};
+
+const V4L2_CTRL_TYPE_INTEGER: u32 = 1;
+const V4L2_CTRL_TYPE_BOOLEAN: u32 = 2;
+const V4L2_CTRL_TYPE_MENU: u32 = 3;
+const V4L2_CTRL_TYPE_INTEGER_MENU: u32 = 9;
+
+const V4L2_CTRL_FLAG_DISABLED: u32 = 0x0001;
+const V4L2_CTRL_FLAG_GRABBED: u32 = 0x0002;
+const V4L2_CTRL_FLAG_READ_ONLY: u32 = 0x0004;
+const V4L2_CTRL_FLAG_INACTIVE: u32 = 0x0010;
+
+#[repr(C)]
+struct V4l2Queryctrl {
+    id: u32,
+    type_: u32,
+    name: [u8; 32],
+    minimum: i32,
+    maximum: i32,
+    step: i32,
+    default_value: i32,
+    flags: u32,
+    reserved: [u32; 2],
+}
+
+#[repr(C)]
+struct V4l2Control {
+    id: u32,
+    value: i32,
+}
+
+#[repr(C, packed)]
+struct V4l2Querymenu {
+    id: u32,
+    index: u32,
+    // An union of the name of the item and, for integer menus, its value.
+    name: [u8; 32],
+    reserved: u32,
+}
+
+/// The `_IOWR('V', nr, T)` ioctl request.
+const fn iowr<T>(nr: u64) -> u64 {
+    (3 << 30) | ((std::mem::size_of::<T>() as u64) << 16) | ((b'V' as u64) << 8) | nr
+}
+
+const VIDIOC_G_CTRL: u64 = iowr::<V4l2Control>(27);
+const VIDIOC_S_CTRL: u64 = iowr::<V4l2Control>(28);
+const VIDIOC_QUERYCTRL: u64 = iowr::<V4l2Queryctrl>(36);
+const VIDIOC_QUERYMENU: u64 = iowr::<V4l2Querymenu>(37);
+
+impl CameraControlId {
+    fn v4l2_id(&self) -> u32 {
+        match self {
+            Self::ExposureTime => 0x009a0902,
+            Self::Gain => 0x00980913,
+            Self::AutoExposure => 0x009a0901,
+            Self::AutoWhiteBalance => 0x0098090c,
+            Self::WhiteBalanceTemperature => 0x0098091a,
+            Self::Focus => 0x009a090a,
+            Self::AutoFocus => 0x009a090c,
+            Self::PowerLineFrequency => 0x00980918,
+            Self::BacklightCompensation => 0x0098091c,
+        }
+    }
+}
+
+/// Calls `ioctl` on `file`, retrying when interrupted.
+///
+/// # Safety
+///
+/// `arg` must be the argument expected by `request`.
+unsafe fn ioctl<T>(file: &File, request: u64, arg: &mut T) -> std::io::Result<()> {
+    loop {
+        if libc::ioctl(file.as_raw_fd(), request as _, arg as *mut T) != -1 {
+            return Ok(());
+        }
+
+        let err = std::io::Error::last_os_error();
+        if err.kind() != std::io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+fn c_string(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+/// The controls of the V4L2 device at `path`, used by Pipewire and the `v4l2`
+/// device provider for most webcams. The device is opened for each operation,
+/// V4L2 allows changing controls while another process streams.
+#[derive(Debug)]
+pub(crate) struct V4l2Controls {
+    path: PathBuf,
+}
+
+impl V4l2Controls {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn open(&self) -> std::io::Result<File> {
+        File::options().read(true).write(true).open(&self.path)
+    }
+
+    fn query(&self, file: &File, id: CameraControlId) -> Option<CameraControl> {
+        let mut query = V4l2Queryctrl {
+            id: id.v4l2_id(),
+            type_: 0,
+            name: [0; 32],
+            minimum: 0,
+            maximum: 0,
+            step: 0,
+            default_value: 0,
+            flags: 0,
+            reserved: [0; 2],
+        };
+        // SAFETY: VIDIOC_QUERYCTRL takes a struct v4l2_queryctrl.
+        unsafe { ioctl(file, VIDIOC_QUERYCTRL, &mut query) }.ok()?;
+        if query.flags & V4L2_CTRL_FLAG_DISABLED != 0 {
+            return None;
+        }
+
+        let control_type = match query.type_ {
+            V4L2_CTRL_TYPE_INTEGER => CameraControlType::Integer,
+            V4L2_CTRL_TYPE_BOOLEAN => CameraControlType::Boolean,
+            V4L2_CTRL_TYPE_MENU | V4L2_CTRL_TYPE_INTEGER_MENU => CameraControlType::Menu,
+            _ => return None,
+        };
+
+        let mut control = V4l2Control {
+            id: query.id,
+            value: 0,
+        };
+        // SAFETY: VIDIOC_G_CTRL takes a struct v4l2_control.
+        if let Err(err) = unsafe { ioctl(file, VIDIOC_G_CTRL, &mut control) } {
+            log::debug!("Could not get control {id:?} of {:?}: {err}", self.path);
+            return None;
+        }
+
+        let menu_items = if control_type == CameraControlType::Menu {
+            self.menu_items(file, &query)
+        } else {
+            Vec::new()
+        };
+
+        Some(CameraControl {
+            id,
+            name: c_string(&query.name),
+            control_type,
+            minimum: query.minimum,
+            maximum: query.maximum,
+            step: query.step,
+            default_value: query.default_value,
+            value: control.value,
+            menu_items,
+            is_read_only: query.flags & (V4L2_CTRL_FLAG_READ_ONLY | V4L2_CTRL_FLAG_GRABBED) != 0,
+            is_inactive: query.flags & V4L2_CTRL_FLAG_INACTIVE != 0,
+        })
+    }
+
+    fn menu_items(&self, file: &File, query: &V4l2Queryctrl) -> Vec<(i32, String)> {
+        (query.minimum..=query.maximum)
+            .filter_map(|index| {
+                let mut item = V4l2Querymenu {
+                    id: query.id,
+                    index: index as u32,
+                    name: [0; 32],
+                    reserved: 0,
+                };
+                // SAFETY: VIDIOC_QUERYMENU takes a struct v4l2_querymenu.
+                // Menus can have gaps, for which it fails.
+                unsafe { ioctl(file, VIDIOC_QUERYMENU, &mut item) }.ok()?;
+
+                let name = if query.type_ == V4L2_CTRL_TYPE_INTEGER_MENU {
+                    let mut value = [0; 8];
+                    value.copy_from_slice(&item.name[..8]);
+                    i64::from_ne_bytes(value).to_string()
+                } else {
+                    c_string(&item.name)
+                };
+
+                Some((index, name))
+            })
+            .collect()
+    }
+}
+
+impl crate::camera_control::ControlBackend for V4l2Controls {
+    fn controls(&self) -> Vec<CameraControl> {
+        let file = match self.open() {
+            Ok(file) => file,
+            Err(err) => {
+                log::debug!(
+                    "Could not open {:?} to query its controls: {err}",
+                    self.path
+                );
+                return Vec::new();
+            }
+        };
+
+        CameraControlId::ALL
+            .iter()
+            .filter_map(|id| self.query(&file, *id))
+            .collect()
+    }
+
+    fn control(&self, id: CameraControlId) -> Option<CameraControl> {
+        let file = match self.open() {
+            Ok(file) => file,
+            Err(err) => {
+                log::debug!(
+                    "Could not open {:?} to query control {id:?}: {err}",
+                    self.path
+                );
+                return None;
+            }
+        };
+
+        self.query(&file, id)
+    }
+
+    fn set(&self, id: CameraControlId, value: i32) -> Result<(), ControlError> {
+        let file = self.open().map_err(|err| {
+            log::error!(
+                "Could not open {:?} to set control {id:?}: {err}",
+                self.path
+            );
+            ControlError::Failed
+        })?;
+
+        let mut control = V4l2Control {
+            id: id.v4l2_id(),
+            value,
+        };
+        // SAFETY: VIDIOC_S_CTRL takes a struct v4l2_control.
+        unsafe { ioctl(&file, VIDIOC_S_CTRL, &mut control) }.map_err(|err| {
+            log::error!("Could not set control {id:?} of {:?}: {err}", self.path);
+            match err.raw_os_error() {
+                Some(libc::EINVAL) => ControlError::NotSupported,
+                Some(libc::ERANGE) => ControlError::OutOfRange,
+                Some(libc::EACCES) => ControlError::ReadOnly,
+                _ => ControlError::Failed,
+            }
+        })
+    }
+}
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use gst::prelude::*;
 use gtk::prelude::*;
@@ -12,6 +13,14 @@ use crate::ViewfinderState;
 
 const BARCODE_TIMEOUT: u32 = 1;
 const PROVIDER_TIMEOUT: u64 = 2;
+/// How long to wait for a frame to reach a grab branch.
+const GRAB_TIMEOUT: Duration = Duration::from_secs(2);
+/// The interval between audio level updates, in nanoseconds.
+const LEVEL_INTERVAL: u64 = 100_000_000;
+/// The tint applied to the camera feed at the warmest colour temperature.
+const COLOR_TEMPERATURE_TINT: f64 = 48.0;
+/// The interval at which the recording limits are checked.
+const RECORDING_LIMITS_INTERVAL: Duration = Duration::from_millis(500);
 
 #[derive(Debug)]
 enum StateChangeState {
@@ -25,6 +34,7 @@ mod imp {
     use std::cell::Cell;
     use std::cell::OnceCell;
     use std::cell::RefCell;
+    use std::collections::VecDeque;
 
     use glib::Properties;
 
@@ -39,21 +49,106 @@ mod imp {
         detect_codes: Cell<bool>,
         #[property(get, set = Self::set_camera, nullable, explicit_notify)]
         camera: RefCell<Option<crate::Camera>>,
+        #[property(get, set = Self::set_mode, nullable, explicit_notify)]
+        mode: RefCell<Option<crate::CameraMode>>,
+        #[property(get, set = Self::set_picture_mode, nullable, explicit_notify)]
+        picture_mode: RefCell<Option<crate::CameraMode>>,
+        #[property(get, set = Self::set_picture_format, explicit_notify, builder(Default::default()))]
+        picture_format: Cell<crate::PictureFormat>,
+        #[property(get, set = Self::set_video_format, explicit_notify, builder(Default::default()))]
+        video_format: Cell<crate::VideoFormat>,
+        pub custom_video_profile: RefCell<Option<gst_pbutils::EncodingContainerProfile>>,
+        #[property(get, set = Self::set_picture_quality, explicit_notify, maximum = 100)]
+        picture_quality: Cell<u32>,
+        #[property(get, set = Self::set_video_bitrate, explicit_notify)]
+        video_bitrate: Cell<u32>,
+        #[property(get, set = Self::set_audio_bitrate, explicit_notify)]
+        audio_bitrate: Cell<u32>,
+        #[property(get, set = Self::set_strip_metadata, explicit_notify)]
+        strip_metadata: Cell<bool>,
+        #[property(get, set, nullable)]
+        geo_location: RefCell<Option<crate::GeoLocation>>,
+        #[property(get, set = Self::set_microphone, nullable, explicit_notify)]
+        microphone: RefCell<Option<crate::Microphone>>,
+        #[property(get, set = Self::set_record_audio, explicit_notify, default = true)]
+        record_audio: Cell<bool>,
+        #[property(get, set = Self::set_monitor_audio, explicit_notify)]
+        monitor_audio: Cell<bool>,
         #[property(get = Self::is_recording, name = "is-recording", type = bool)]
         pub is_recording_video: RefCell<Option<PathBuf>>,
+        #[property(get = Self::is_recording_paused, name = "is-recording-paused", type = bool)]
+        pub recording_pause: crate::RecordingPause,
+        #[property(get, set)]
+        max_recording_duration: Cell<u32>,
+        #[property(get, set)]
+        max_recording_size: Cell<u64>,
+        #[property(get, set, builder(Default::default()))]
+        recording_limit_action: Cell<crate::RecordingLimitAction>,
+        #[property(get, set, minimum = 1, default = 60)]
+        loop_segment_duration: Cell<u32>,
+        #[property(get, set, minimum = 1, default = 300)]
+        loop_duration: Cell<u32>,
+        #[property(get)]
+        is_loop_recording: Cell<bool>,
+        #[property(get, set = Self::set_timelapse_interval, explicit_notify)]
+        timelapse_interval: Cell<u32>,
+        #[property(get, set, minimum = 1, default = 30)]
+        timelapse_framerate: Cell<u32>,
+        pub timelapse: crate::Timelapse,
+        #[property(get, set = Self::set_effect, explicit_notify, builder(Default::default()))]
+        effect: Cell<crate::VideoEffect>,
+        #[property(get, set = Self::set_brightness, explicit_notify, minimum = -1.0, maximum = 1.0)]
+        brightness: Cell<f64>,
+        #[property(get, set = Self::set_contrast, explicit_notify, minimum = 0.0, maximum = 2.0, default = 1.0)]
+        contrast: Cell<f64>,
+        #[property(get, set = Self::set_saturation, explicit_notify, minimum = 0.0, maximum = 2.0, default = 1.0)]
+        saturation: Cell<f64>,
+        #[property(get, set = Self::set_hue, explicit_notify, minimum = -1.0, maximum = 1.0)]
+        hue: Cell<f64>,
+        #[property(get, set = Self::set_color_temperature, explicit_notify, minimum = -1.0, maximum = 1.0)]
+        color_temperature: Cell<f64>,
+        pub color_balance: crate::ColorBalance,
 
         pub zbar_branch: RefCell<Option<gst::Element>>,
+        // The branches added with `add_branch`.
+        pub branches: RefCell<Vec<gst::Element>>,
+        pub frame_processors: crate::FrameProcessors,
+        pub burst: RefCell<Option<crate::Burst>>,
+        // The branch taking a picture while recording.
+        pub still: RefCell<Option<crate::Burst>>,
         pub devices: OnceCell<crate::DeviceProvider>,
         pub camera_src: RefCell<Option<gst::Element>>,
         pub camerabin: OnceCell<gst::Element>,
-        pub camera_element: OnceCell<gst::Element>,
-        pub capsfilter: OnceCell<gst::Element>,
+        pub camera_element: RefCell<Option<gst::Element>>,
+        pub capsfilter: RefCell<Option<gst::Element>>,
         pub sink_paintable: OnceCell<gst::Element>,
         pub tee: OnceCell<crate::PipelineTee>,
         pub bus_watch: OnceCell<gst::bus::BusWatchGuard>,
+        // The pipeline measuring the audio level while not recording.
+        pub audio_monitor: RefCell<Option<(gst::Pipeline, gst::bus::BusWatchGuard)>>,
+        // Set within `reconfigure`, to whether the stream has to be restarted
+        // once all the changes are applied.
+        pub pending_restart: Cell<Option<bool>>,
 
         pub is_stopping_recording: Cell<bool>,
+        // The location passed to `start_recording`, after which the segments
+        // of the recording are named.
+        pub recording_location: RefCell<Option<PathBuf>>,
+        pub recording_segment: Cell<u32>,
+        // The running time at which the current segment started.
+        pub segment_started_at: Cell<Option<gst::ClockTime>>,
+        // Set while the recording is stopped to continue in a new segment.
+        pub is_splitting_recording: Cell<bool>,
+        pub recording_limits_source: RefCell<Option<glib::SourceId>>,
+        // The time-lapse interval set during the recording, applied once it
+        // ends.
+        pub pending_timelapse_interval: Cell<Option<u32>>,
+        // The finished segments of the loop recording, from oldest to newest.
+        pub loop_segments: RefCell<VecDeque<PathBuf>>,
+        pub is_saving_loop: Cell<bool>,
         pub is_taking_picture: Cell<bool>,
+        // The mode of the picture being taken.
+        pub capture_mode: RefCell<Option<crate::CameraMode>>,
         pub is_front_camera: Cell<bool>,
 
         pub timeout_handler: RefCell<Option<glib::SourceId>>,
@@ -76,6 +171,10 @@ mod imp {
             self.is_recording_video.borrow().is_some()
         }
 
+        fn is_recording_paused(&self) -> bool {
+            self.recording_pause.is_paused()
+        }
+
         fn detect_codes(&self) -> bool {
             self.zbar_branch.borrow().is_some()
         }
@@ -122,10 +221,19 @@ mod imp {
                 return;
             }
 
+            if self.burst.borrow().is_some() {
+                log::error!("Could not set camera, we are taking a burst");
+                return;
+            }
+
             if camera == self.camera.replace(camera.clone()) {
                 return;
             }
 
+            // Modes are specific to a camera.
+            let mode_changed = self.mode.take().is_some();
+            let picture_mode_changed = self.picture_mode.take().is_some();
+
             // We reset to READY if we landed on the ERROR state on the previous
             // camera.
             if matches!(obj.state(), ViewfinderState::Error) {
@@ -163,6 +271,327 @@ mod imp {
             }
 
             obj.notify_camera();
+            if mode_changed {
+                obj.notify_mode();
+            }
+            if picture_mode_changed {
+                obj.notify_picture_mode();
+            }
+        }
+
+        /// Sets the mode of the current camera, `None` picks one automatically.
+        fn set_mode(&self, mode: Option<crate::CameraMode>) {
+            let obj = self.obj();
+
+            if self.is_taking_picture.get() {
+                log::error!("Could not set mode, we are taking a picture");
+                return;
+            }
+
+            if self.is_recording_video.borrow().is_some() {
+                log::error!("Could not set mode, there is a recording in progress");
+                return;
+            }
+
+            if let Some(ref mode) = mode {
+                if !self.is_mode_supported(mode) {
+                    log::error!("Could not set mode, {mode} is not supported by the camera");
+                    return;
+                }
+            }
+
+            if mode == self.mode.replace(mode.clone()) {
+                return;
+            }
+
+            obj.update_caps();
+
+            obj.notify_mode();
+        }
+
+        /// Sets the mode used to take pictures, `None` uses the mode of the
+        /// viewfinder.
+        fn set_picture_mode(&self, mode: Option<crate::CameraMode>) {
+            let obj = self.obj();
+
+            if self.is_taking_picture.get() {
+                log::error!("Could not set picture mode, we are taking a picture");
+                return;
+            }
+
+            if let Some(ref mode) = mode {
+                if !self.is_mode_supported(mode) {
+                    log::error!(
+                        "Could not set picture mode, {mode} is not supported by the camera"
+                    );
+                    return;
+                }
+            }
+
+            if mode == self.picture_mode.replace(mode.clone()) {
+                return;
+            }
+
+            obj.update_caps();
+
+            obj.notify_picture_mode();
+        }
+
+        fn set_picture_format(&self, format: crate::PictureFormat) {
+            let obj = self.obj();
+
+            if self.is_taking_picture.get() {
+                log::error!("Could not set picture format, we are taking a picture");
+                return;
+            }
+
+            if !format.is_supported() {
+                log::error!("Could not set picture format, {format:?} is not supported");
+                return;
+            }
+
+            if format == self.picture_format.replace(format) {
+                return;
+            }
+
+            obj.setup_picture_profile();
+
+            obj.notify_picture_format();
+        }
+
+        fn set_video_format(&self, format: crate::VideoFormat) {
+            let obj = self.obj();
+
+            if self.is_recording_video.borrow().is_some() {
+                log::error!("Could not set video format, there is a recording in progress");
+                return;
+            }
+
+            if !format.is_supported() {
+                log::error!("Could not set video format, {format:?} is not supported");
+                return;
+            }
+
+            let had_custom_profile = self.custom_video_profile.take().is_some();
+            if format == self.video_format.replace(format) && !had_custom_profile {
+                return;
+            }
+
+            obj.setup_recording();
+
+            obj.notify_video_format();
+        }
+
+        fn set_picture_quality(&self, quality: u32) {
+            let obj = self.obj();
+
+            if self.is_taking_picture.get() {
+                log::error!("Could not set picture quality, we are taking a picture");
+                return;
+            }
+
+            if quality == self.picture_quality.replace(quality) {
+                return;
+            }
+
+            obj.setup_picture_profile();
+
+            obj.notify_picture_quality();
+        }
+
+        fn set_video_bitrate(&self, bitrate: u32) {
+            let obj = self.obj();
+
+            if self.is_recording_video.borrow().is_some() {
+                log::error!("Could not set video bitrate, there is a recording in progress");
+                return;
+            }
+
+            if bitrate == self.video_bitrate.replace(bitrate) {
+                return;
+            }
+
+            obj.setup_recording();
+
+            obj.notify_video_bitrate();
+        }
+
+        fn set_audio_bitrate(&self, bitrate: u32) {
+            let obj = self.obj();
+
+            if self.is_recording_video.borrow().is_some() {
+                log::error!("Could not set audio bitrate, there is a recording in progress");
+                return;
+            }
+
+            if bitrate == self.audio_bitrate.replace(bitrate) {
+                return;
+            }
+
+            obj.setup_recording();
+
+            obj.notify_audio_bitrate();
+        }
+
+        fn set_strip_metadata(&self, strip_metadata: bool) {
+            let obj = self.obj();
+
+            if self.is_taking_picture.get() {
+                log::error!("Could not set strip metadata, we are taking a picture");
+                return;
+            }
+
+            if strip_metadata == self.strip_metadata.replace(strip_metadata) {
+                return;
+            }
+
+            obj.setup_picture_profile();
+
+            obj.notify_strip_metadata();
+        }
+
+        fn set_microphone(&self, microphone: Option<crate::Microphone>) {
+            let obj = self.obj();
+
+            if self.is_recording_video.borrow().is_some() {
+                log::error!("Could not set microphone, there is a recording in progress");
+                return;
+            }
+
+            if microphone == self.microphone.replace(microphone.clone()) {
+                return;
+            }
+
+            obj.setup_audio_source();
+            obj.update_audio_monitor();
+
+            obj.notify_microphone();
+        }
+
+        fn set_record_audio(&self, record_audio: bool) {
+            let obj = self.obj();
+
+            if self.is_recording_video.borrow().is_some() {
+                log::error!("Could not set record audio, there is a recording in progress");
+                return;
+            }
+
+            if record_audio == self.record_audio.replace(record_audio) {
+                return;
+            }
+
+            obj.setup_recording();
+            obj.update_audio_monitor();
+
+            obj.notify_record_audio();
+        }
+
+        fn set_timelapse_interval(&self, interval: u32) {
+            let obj = self.obj();
+
+            if self.is_recording_video.borrow().is_some() {
+                log::debug!("Setting the time-lapse interval once the recording ends");
+                self.pending_timelapse_interval.set(Some(interval));
+                return;
+            }
+            self.pending_timelapse_interval.take();
+
+            let old_interval = self.timelapse_interval.replace(interval);
+            if old_interval == interval {
+                return;
+            }
+
+            // Time-lapses are recorded without audio.
+            if (old_interval > 0) != (interval > 0) {
+                obj.setup_recording();
+                obj.update_audio_monitor();
+            }
+
+            obj.notify_timelapse_interval();
+        }
+
+        fn set_effect(&self, effect: crate::VideoEffect) {
+            if effect == self.effect.replace(effect) {
+                return;
+            }
+
+            let obj = self.obj();
+            obj.update_color_balance();
+
+            obj.notify_effect();
+        }
+
+        fn set_brightness(&self, brightness: f64) {
+            if brightness == self.brightness.replace(brightness) {
+                return;
+            }
+
+            let obj = self.obj();
+            obj.update_color_balance();
+
+            obj.notify_brightness();
+        }
+
+        fn set_contrast(&self, contrast: f64) {
+            if contrast == self.contrast.replace(contrast) {
+                return;
+            }
+
+            let obj = self.obj();
+            obj.update_color_balance();
+
+            obj.notify_contrast();
+        }
+
+        fn set_saturation(&self, saturation: f64) {
+            if saturation == self.saturation.replace(saturation) {
+                return;
+            }
+
+            let obj = self.obj();
+            obj.update_color_balance();
+
+            obj.notify_saturation();
+        }
+
+        fn set_hue(&self, hue: f64) {
+            if hue == self.hue.replace(hue) {
+                return;
+            }
+
+            let obj = self.obj();
+            obj.update_color_balance();
+
+            obj.notify_hue();
+        }
+
+        fn set_color_temperature(&self, color_temperature: f64) {
+            if color_temperature == self.color_temperature.replace(color_temperature) {
+                return;
+            }
+
+            let obj = self.obj();
+            obj.update_color_balance();
+
+            obj.notify_color_temperature();
+        }
+
+        fn set_monitor_audio(&self, monitor_audio: bool) {
+            if monitor_audio == self.monitor_audio.replace(monitor_audio) {
+                return;
+            }
+
+            let obj = self.obj();
+            obj.update_audio_monitor();
+
+            obj.notify_monitor_audio();
+        }
+
+        fn is_mode_supported(&self, mode: &crate::CameraMode) -> bool {
+            self.camera
+                .borrow()
+                .as_ref()
+                .is_some_and(|camera| camera.supports_mode(mode))
         }
     }
 
@@ -282,8 +711,30 @@ mod imp {
                 }
             }));
 
+            devices.microphones().connect_items_changed(
+                glib::clone!(@weak obj => move |microphones, _, _, _| {
+                    let Some(microphone) = obj.microphone() else {
+                        return;
+                    };
+                    let is_available = microphones
+                        .iter::<crate::Microphone>()
+                        .any(|m| m.is_ok_and(|m| m == microphone));
+                    if !is_available && !obj.is_recording() {
+                        obj.set_microphone(None::<crate::Microphone>);
+                    }
+                }),
+            );
+
+            self.record_audio.set(true);
+            self.loop_segment_duration.set(60);
+            self.loop_duration.set(300);
+            self.timelapse_framerate.set(30);
+            self.contrast.set(1.0);
+            self.saturation.set(1.0);
+
             log::debug!("Setup recording");
             obj.setup_recording();
+            obj.setup_picture_profile();
         }
 
         fn dispose(&self) {
@@ -295,6 +746,9 @@ mod imp {
             if let Err(err) = self.camerabin().set_state(gst::State::Null) {
                 log::error!("Could not stop camerabin: {err}");
             }
+            if let Some((pipeline, _)) = self.audio_monitor.take() {
+                let _ = pipeline.set_state(gst::State::Null);
+            }
 
             self.picture.unparent();
         }
@@ -305,7 +759,10 @@ mod imp {
                     // These are emitted whenever the saving process finishes,
                     // successful or not.
                     glib::subclass::Signal::builder("picture-done")
-                        .param_types([Option::<gio::File>::static_type()])
+                        .param_types([
+                            Option::<gio::File>::static_type(),
+                            Option::<crate::CameraMode>::static_type(),
+                        ])
                         .build(),
                     glib::subclass::Signal::builder("recording-done")
                         .param_types([Option::<gio::File>::static_type()])
@@ -313,6 +770,15 @@ mod imp {
                     glib::subclass::Signal::builder("code-detected")
                         .param_types([crate::CodeType::static_type(), String::static_type()])
                         .build(),
+                    glib::subclass::Signal::builder("audio-level")
+                        .param_types([crate::AudioLevel::static_type()])
+                        .build(),
+                    glib::subclass::Signal::builder("burst-progress")
+                        .param_types([u32::static_type(), gio::File::static_type()])
+                        .build(),
+                    glib::subclass::Signal::builder("burst-done")
+                        .param_types([gio::ListModel::static_type()])
+                        .build(),
                 ]
             });
             SIGNALS.as_ref()
@@ -400,59 +866,286 @@ glib::wrapper! {
     ///
     /// To safely switch cameras, the current [`fn@Viewfinder::state`] must be in [`ViewfinderState::Ready`][crate::ViewfinderState::Ready].
     /// This is because switching camera sources would interrupt most active operations, if any are present.
+    /// The camera cannot be switched while taking a picture or a burst, or while recording.
     ///
     ///  Readable | Nullable
     ///
     ///
-    /// ## Signals
+    /// #### `mode`
+    ///  The [`CameraMode`][crate::CameraMode] used by the current camera.
+    /// When `None`, a mode is picked automatically, preferring the highest
+    /// resolution up to 1080p at up to 30 frames per second.
     ///
+    /// Changing the mode renegotiates the camera feed without restarting it. It is reset to `None`
+    /// when the camera changes, and cannot be changed while taking a picture or recording.
     ///
-    /// #### `picture-done`
-    ///  This signal is emitted after a picture has been taken and saved.
-    /// Note that this signal is emitted even if saving the picture failed, and should not be used
-    /// to detect if the picture was successfully saved.
+    ///  Readable | Writeable | Nullable
     ///
     ///
-    /// #### `recording-done`
-    ///  This signal is emitted after a recording has finished and been saved.
-    /// Note that this signal is emitted even if saving the recording failed, and should not be used
-    /// to detect if the recoding was successfully saved.
+    /// #### `picture-mode`
+    ///  The [`CameraMode`][crate::CameraMode] used to take pictures.
+    /// When `None`, pictures are taken with the mode of the viewfinder. Otherwise the camera
+    /// switches to this mode while taking a picture, which allows e.g. for a smooth viewfinder at
+    /// a low resolution and pictures at the highest resolution of the camera.
     ///
+    /// It is reset to `None` when the camera changes, and cannot be changed while taking a picture.
     ///
-    /// #### `code-detected`
-    ///  This signal is emitted when a barcode is detected in the camera feed.
-    /// This will only be emitted if [`detect-codes`](#detect-codes) is `true`.
+    ///  Readable | Writeable | Nullable
     ///
-    /// Barcodes are only detected when they appear on the feed, not on every frame when they are visible.
     ///
-    /// # Implements
+    /// #### `picture-format`
+    ///  The [`PictureFormat`][crate::PictureFormat] in which pictures are saved.
+    /// Only formats for which [`PictureFormat::is_supported()`][crate::PictureFormat::is_supported()]
+    /// returns `true` can be set. Changing the format restarts the camera feed.
     ///
-    /// [`gtk::prelude::WidgetExt`][trait@gtk::prelude::WidgetExt], [`glib::ObjectExt`][trait@gtk::glib::ObjectExt]
-    pub struct Viewfinder(ObjectSubclass<imp::Viewfinder>)
-        @extends gtk::Widget;
-}
-
-impl Default for Viewfinder {
-    fn default() -> Self {
-        glib::Object::new()
-    }
-}
-
-impl Viewfinder {
-    /// Creates a new [`Viewfinder`][crate::Viewfinder]
+    ///  Readable | Writeable
     ///
-    /// # Returns
     ///
-    /// a new Viewfinder
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    /// Gets the aspect ratio of the camera output.
+    /// #### `video-format`
+    ///  The [`VideoFormat`][crate::VideoFormat] in which videos are recorded.
+    /// Only formats for which [`VideoFormat::is_supported()`][crate::VideoFormat::is_supported()]
+    /// returns `true` can be set. Setting it discards the profile set with
+    /// [`fn@Viewfinder::set_video_profile`], and restarts the camera feed.
     ///
-    /// # Returns
+    ///  Readable | Writeable
     ///
-    /// an aspect ratio calculated with width/height, or 0 for no valid aspect
+    ///
+    /// #### `picture-quality`
+    ///  The quality of the saved pictures, from 1 to 100, for the formats that support it.
+    /// `0` uses the default quality of the encoder. Changing it restarts the camera feed.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `video-bitrate`
+    ///  The target bitrate of the recorded video, in kbit/s. `0` uses the default bitrate of the
+    /// encoder. It does not apply to profiles set with [`fn@Viewfinder::set_video_profile`].
+    /// Changing it restarts the camera feed.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `audio-bitrate`
+    ///  The target bitrate of the recorded audio, in kbit/s. `0` uses the default bitrate of the
+    /// encoder. It does not apply to profiles set with [`fn@Viewfinder::set_video_profile`].
+    /// Changing it restarts the camera feed.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `microphone`
+    ///  The [`Microphone`][crate::Microphone] recorded along videos. If `None`, the default audio
+    /// source of the system is used. It is reset to `None` when the microphone is disconnected.
+    /// Changing it restarts the camera feed.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `record-audio`
+    ///  Whether to record audio along videos, `true` by default. When disabled, videos are recorded
+    /// without an audio track. Changing it restarts the camera feed.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `monitor-audio`
+    ///  Whether to measure the audio level while not recording, emitting
+    /// [`audio-level`](#audio-level). The audio level is always measured while recording.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `strip-metadata`
+    ///  Whether to save pictures and videos without metadata.
+    /// By default, JPEG pictures carry EXIF and XMP metadata with the date, the application, the
+    /// camera model and orientation, plus the exposure mode, exposure time and white balance mode
+    /// when they are reported by the [controls][crate::Camera::controls] of the camera.
+    /// Changing it restarts the camera feed.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `geo-location`
+    ///  The [`GeoLocation`][crate::GeoLocation] written as geo tags into the pictures and videos
+    /// taken afterwards, unless `strip-metadata` is set.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `max-recording-duration`
+    ///  The maximum duration of a recording, in seconds, not counting the time spent paused.
+    /// `0`, the default, means that there is no limit. See
+    /// [`recording-limit-action`](#recording-limit-action).
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `max-recording-size`
+    ///  The maximum size of a recorded file, in bytes. `0`, the default, means that there is no
+    /// limit. The size is checked periodically, so files can get slightly bigger than the limit.
+    /// See [`recording-limit-action`](#recording-limit-action).
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `recording-limit-action`
+    ///  The [`RecordingLimitAction`][crate::RecordingLimitAction] taken when a recording reaches
+    /// [`max-recording-duration`](#max-recording-duration) or
+    /// [`max-recording-size`](#max-recording-size). When splitting, the limits apply to each file,
+    /// and [`recording-done`](#recording-done) is emitted for each of them.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `loop-segment-duration`
+    ///  The duration of the files written by a loop recording, in seconds, `60` by default. See
+    /// [`fn@Viewfinder::start_loop_recording`].
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `loop-duration`
+    ///  The duration of the footage kept by a loop recording, in seconds, `300` by default. Older
+    /// files are deleted. See [`fn@Viewfinder::start_loop_recording`].
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `is-loop-recording`
+    ///  Whether the recording in progress was started with
+    /// [`fn@Viewfinder::start_loop_recording`].
+    ///
+    ///  Readable
+    ///
+    ///
+    /// #### `timelapse-interval`
+    ///  The interval between the frames of time-lapse recordings, in milliseconds. When not `0`,
+    /// recordings are time-lapses, keeping one frame of the camera feed every interval, to be
+    /// played at [`timelapse-framerate`](#timelapse-framerate). Time-lapses are recorded without
+    /// audio. Changing it from or to `0` restarts the camera feed. Changes made during a recording
+    /// are applied once it ends, the property keeping its value until then.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `timelapse-framerate`
+    ///  The frame rate at which time-lapse recordings play, `30` by default.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `effect`
+    ///  The [`VideoEffect`][crate::VideoEffect] applied to the camera feed, and thus to pictures and
+    /// recordings.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `brightness`
+    ///  The brightness adjustment of the camera feed, from `-1.0` to `1.0`, `0.0` by default. Like
+    /// the other adjustments, it is applied in software and works with every camera.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `contrast`
+    ///  The contrast adjustment of the camera feed, from `0.0` to `2.0`, `1.0` by default.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `saturation`
+    ///  The saturation adjustment of the camera feed, from `0.0` to `2.0`, `1.0` by default.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `hue`
+    ///  The hue adjustment of the camera feed, from `-1.0` to `1.0`, `0.0` by default.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `color-temperature`
+    ///  The colour temperature adjustment of the camera feed, from `-1.0` to `1.0`, `0.0` by
+    /// default. Positive values make colours warmer, negative ones cooler.
+    ///
+    ///  Readable | Writeable
+    ///
+    ///
+    /// #### `is-recording-paused`
+    ///  Whether the recording in progress is paused, see [`fn@Viewfinder::pause_recording`].
+    ///
+    ///  Readable
+    ///
+    ///
+    /// ## Signals
+    ///
+    ///
+    /// #### `picture-done`
+    ///  This signal is emitted after a picture has been taken and saved, with the
+    /// [`CameraMode`][crate::CameraMode] it was taken with, if known.
+    /// Note that this signal is emitted even if saving the picture failed, and should not be used
+    /// to detect if the picture was successfully saved.
+    ///
+    ///
+    /// #### `recording-done`
+    ///  This signal is emitted after a recording has finished and been saved. When the recording is
+    /// split, it is emitted for each of its files.
+    /// Note that this signal is emitted even if saving the recording failed, and should not be used
+    /// to detect if the recoding was successfully saved.
+    ///
+    ///
+    /// #### `code-detected`
+    ///  This signal is emitted when a barcode is detected in the camera feed.
+    /// This will only be emitted if [`detect-codes`](#detect-codes) is `true`.
+    ///
+    /// Barcodes are only detected when they appear on the feed, not on every frame when they are visible.
+    ///
+    ///
+    /// #### `audio-level`
+    ///  This signal is emitted periodically with the [`AudioLevel`][crate::AudioLevel] of the
+    /// microphone, while recording with [`record-audio`](#record-audio) set or while
+    /// [`monitor-audio`](#monitor-audio) is `true`.
+    ///
+    ///
+    /// #### `burst-progress`
+    ///  This signal is emitted after each picture of a burst is saved, with its number within the
+    /// burst, starting at 1, and its file. See [`fn@Viewfinder::take_burst`].
+    ///
+    ///
+    /// #### `burst-done`
+    ///  This signal is emitted after a burst has finished, with a [`gio::ListModel`] of the files
+    /// that were saved. See [`fn@Viewfinder::take_burst`].
+    ///
+    /// # Implements
+    ///
+    /// [`gtk::prelude::WidgetExt`][trait@gtk::prelude::WidgetExt], [`glib::ObjectExt`][trait@gtk::glib::ObjectExt]
+    pub struct Viewfinder(ObjectSubclass<imp::Viewfinder>)
+        @extends gtk::Widget;
+}
+
+impl Default for Viewfinder {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl Viewfinder {
+    /// Creates a new [`Viewfinder`][crate::Viewfinder]
+    ///
+    /// # Returns
+    ///
+    /// a new Viewfinder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the aspect ratio of the camera output.
+    ///
+    /// # Returns
+    ///
+    /// an aspect ratio calculated with width/height, or 0 for no valid aspect
     /// ratio.
     pub fn aspect_ratio(&self) -> f64 {
         let imp = self.imp();
@@ -465,12 +1158,16 @@ impl Viewfinder {
 
     /// Takes a picture.
     ///
-    /// The recording will be saved to `location`. This method throws an error
+    /// The picture will be saved to `location`. This method throws an error
     /// if:
-    ///  - we are already recording or taking a picture
+    ///  - we are already taking a picture
     ///  - the [`fn@Viewfinder::state`] of the camera is not
     ///    [`ViewfinderState::Ready`][crate::ViewfinderState::Ready].
     ///
+    /// While recording, the picture is taken from the camera feed without
+    /// interrupting the recording, at the resolution of the video, and saved
+    /// as JPEG regardless of [`picture-format`](#picture-format).
+    ///
     /// This operation may take a while. The resolution might be changed
     /// temporarily, autofocusing might take place, etc. Basically
     /// everything you'd expect to happen when you click the photo button in
@@ -490,11 +1187,13 @@ impl Viewfinder {
         }
 
         if imp.is_recording_video.borrow().is_some() {
-            return Err(crate::CaptureError::RecordingInProgress);
+            return self.take_still(location.as_ref());
         }
 
         // Set after we cannot fail anymore.
         imp.is_taking_picture.set(true);
+        imp.capture_mode
+            .replace(self.picture_mode().or_else(|| self.negotiated_mode()));
 
         self.set_tags();
 
@@ -506,6 +1205,264 @@ impl Viewfinder {
         Ok(())
     }
 
+    // Takes a picture from the camera feed, used while recording since
+    // camerabin cannot take pictures in video mode.
+    fn take_still(&self, location: &Path) -> Result<(), crate::CaptureError> {
+        let imp = self.imp();
+
+        let profile = picture_profile(
+            crate::PictureFormat::Jpeg,
+            self.picture_quality(),
+            self.strip_metadata(),
+        );
+        let still = crate::Burst::single(location, &profile).map_err(|err| {
+            log::error!("Could not create still branch: {err}");
+            crate::CaptureError::StillUnavailable
+        })?;
+
+        imp.is_taking_picture.set(true);
+        imp.capture_mode.replace(self.negotiated_mode());
+
+        imp.tee.get().unwrap().add_leaky_branch(still.bin());
+        imp.still.replace(Some(still));
+
+        Ok(())
+    }
+
+    /// Takes a burst of `count` pictures, at least `interval` apart.
+    ///
+    /// The pictures are taken from the camera feed, which is not interrupted
+    /// nor renegotiated, and saved in the [`picture-format`](#picture-format).
+    /// They are named after `stem`, a path without extension, followed by
+    /// their number and the extension of the format, e.g. `Burst 001.jpeg`
+    /// for the `Burst` stem.
+    /// Bursts are saved as JPEG when the format is
+    /// [`Avif`][crate::PictureFormat::Avif], as `avifmux` cannot split its
+    /// output into several files. This method throws an error if:
+    ///  - we are already taking a burst
+    ///  - the [`fn@Viewfinder::state`] of the camera is not
+    ///    [`ViewfinderState::Ready`][crate::ViewfinderState::Ready].
+    ///
+    /// The [`burst-progress`](#burst-progress) signal is emitted for each
+    /// saved picture, and [`burst-done`](#burst-done) when this operation
+    /// ends.
+    pub fn take_burst<P: AsRef<Path>>(
+        &self,
+        count: u32,
+        interval: Duration,
+        stem: P,
+    ) -> Result<(), crate::CaptureError> {
+        let imp = self.imp();
+
+        if !matches!(self.state(), ViewfinderState::Ready) {
+            return Err(crate::CaptureError::NotReady);
+        }
+
+        if imp.burst.borrow().is_some() {
+            return Err(crate::CaptureError::BurstInProgress);
+        }
+
+        let format = match self.picture_format() {
+            crate::PictureFormat::Avif => crate::PictureFormat::Jpeg,
+            format => format,
+        };
+        let profile = picture_profile(format, self.picture_quality(), self.strip_metadata());
+        let burst = crate::Burst::new(
+            stem.as_ref(),
+            format.extension(),
+            &profile,
+            count.max(1),
+            interval,
+        )
+        .map_err(|err| {
+            log::error!("Could not create burst branch: {err}");
+            crate::CaptureError::BurstUnavailable
+        })?;
+
+        imp.tee.get().unwrap().add_leaky_branch(burst.bin());
+        imp.burst.replace(Some(burst));
+
+        Ok(())
+    }
+
+    /// Grabs the next frame of the camera feed.
+    ///
+    /// The frame is taken from the feed, which is not interrupted nor
+    /// renegotiated, and kept in memory. See
+    /// [`fn@Viewfinder::grab_sample`] for the errors of this method.
+    ///
+    /// # Returns
+    ///
+    /// the frame, at the resolution of the feed.
+    pub async fn grab_frame(&self) -> Result<gdk::Texture, crate::CaptureError> {
+        let sample = self.grab_sample().await?;
+
+        crate::frame_grab::texture_from_sample(&sample).ok_or_else(|| {
+            log::error!("Could not create texture from sample: {sample:?}");
+            crate::CaptureError::FrameUnavailable
+        })
+    }
+
+    /// Grabs the next frame of the camera feed as a [`gst::Sample`].
+    ///
+    /// The sample holds a raw RGBA video frame and its caps. This method
+    /// throws an error if:
+    ///  - the [`fn@Viewfinder::state`] of the camera is not
+    ///    [`ViewfinderState::Ready`][crate::ViewfinderState::Ready]
+    ///  - no frame reaches the viewfinder within a few seconds.
+    pub async fn grab_sample(&self) -> Result<gst::Sample, crate::CaptureError> {
+        let imp = self.imp();
+
+        if !matches!(self.state(), ViewfinderState::Ready) {
+            return Err(crate::CaptureError::NotReady);
+        }
+
+        let grab = crate::FrameGrab::new().map_err(|err| {
+            log::error!("Could not create frame grab branch: {err}");
+            crate::CaptureError::FrameUnavailable
+        })?;
+        let branch = grab.bin().clone();
+
+        let tee = imp.tee.get().unwrap();
+        tee.add_leaky_branch(&branch);
+        let sample = glib::future_with_timeout(GRAB_TIMEOUT, grab.sample()).await;
+        tee.remove_branch(&branch);
+
+        match sample {
+            Ok(Some(sample)) => Ok(sample),
+            Ok(None) => Err(crate::CaptureError::FrameUnavailable),
+            Err(_) => {
+                log::error!("No frame reached the viewfinder in {GRAB_TIMEOUT:?}");
+                Err(crate::CaptureError::FrameUnavailable)
+            }
+        }
+    }
+
+    /// Adds a branch to the camera feed.
+    ///
+    /// `branch` receives the raw frames shown by the viewfinder, through its
+    /// sink pad, until it is removed with [`fn@Viewfinder::remove_branch`].
+    /// This can be used to analyze the feed, for example. The branch must not
+    /// block the stream: it runs in its own thread, but frames are dropped
+    /// while it is busy. This method throws an error if `branch` is already
+    /// part of a pipeline or has no sink pad.
+    pub fn add_branch(&self, branch: &impl IsA<gst::Element>) -> Result<(), crate::CaptureError> {
+        let imp = self.imp();
+        let branch = branch.upcast_ref::<gst::Element>();
+
+        if branch.parent().is_some() || branch.sink_pads().is_empty() {
+            return Err(crate::CaptureError::InvalidBranch);
+        }
+
+        imp.tee.get().unwrap().add_leaky_branch(branch);
+        imp.branches.borrow_mut().push(branch.clone());
+
+        Ok(())
+    }
+
+    /// Removes a branch added with [`fn@Viewfinder::add_branch`] or
+    /// [`fn@Viewfinder::add_frame_callback`].
+    ///
+    /// The branch is unlinked and set to the [`Null`][gst::State::Null]
+    /// state asynchronously, once no frame is flowing through it.
+    pub fn remove_branch(
+        &self,
+        branch: &impl IsA<gst::Element>,
+    ) -> Result<(), crate::CaptureError> {
+        let imp = self.imp();
+        let branch = branch.upcast_ref::<gst::Element>();
+
+        let mut branches = imp.branches.borrow_mut();
+        let Some(position) = branches.iter().position(|b| b == branch) else {
+            return Err(crate::CaptureError::NoBranchToRemove);
+        };
+        branches.remove(position);
+
+        imp.tee.get().unwrap().remove_branch(branch);
+
+        Ok(())
+    }
+
+    /// Calls `callback` with frames of the camera feed, converted to `format`,
+    /// at most one every `interval`.
+    ///
+    /// The callback is called from a streaming thread, frames are dropped
+    /// while it runs. It is called until the returned branch is removed with
+    /// [`fn@Viewfinder::remove_branch`].
+    ///
+    /// # Returns
+    ///
+    /// the branch calling `callback`.
+    pub fn add_frame_callback<F>(
+        &self,
+        format: gst_video::VideoFormat,
+        interval: Duration,
+        callback: F,
+    ) -> Result<gst::Element, crate::CaptureError>
+    where
+        F: Fn(&crate::Frame) + Send + Sync + 'static,
+    {
+        let branch = crate::frame_callback::create_frame_callback_bin(format, interval, callback)
+            .map_err(|err| {
+            log::error!("Could not create frame callback branch: {err}");
+            crate::CaptureError::FrameUnavailable
+        })?;
+        self.add_branch(&branch)?;
+
+        Ok(branch)
+    }
+
+    /// Adds a processor modifying the frames of the camera feed.
+    ///
+    /// Processors are applied in the order they were added, to the frames
+    /// shown by the viewfinder as well as to pictures and recordings. They are
+    /// kept when the camera changes.
+    ///
+    /// # Returns
+    ///
+    /// the identifier of the processor, to toggle or remove it.
+    pub fn add_frame_processor(
+        &self,
+        processor: impl crate::FrameProcessor,
+    ) -> crate::FrameProcessorId {
+        self.imp().frame_processors.add(processor)
+    }
+
+    /// Removes a processor added with [`fn@Viewfinder::add_frame_processor`].
+    pub fn remove_frame_processor(
+        &self,
+        id: crate::FrameProcessorId,
+    ) -> Result<(), crate::CaptureError> {
+        if self.imp().frame_processors.remove(id) {
+            Ok(())
+        } else {
+            Err(crate::CaptureError::NoFrameProcessor)
+        }
+    }
+
+    /// Enables or disables a processor, disabled processors keep their place
+    /// in the chain.
+    pub fn set_frame_processor_enabled(
+        &self,
+        id: crate::FrameProcessorId,
+        enabled: bool,
+    ) -> Result<(), crate::CaptureError> {
+        if self.imp().frame_processors.set_enabled(id, enabled) {
+            Ok(())
+        } else {
+            Err(crate::CaptureError::NoFrameProcessor)
+        }
+    }
+
+    /// Whether a processor is enabled.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the processor was not added to the viewfinder.
+    pub fn is_frame_processor_enabled(&self, id: crate::FrameProcessorId) -> Option<bool> {
+        self.imp().frame_processors.is_enabled(id)
+    }
+
     /// Starts recording a video.
     ///
     /// The recording will be saved to `location`. This method throws an error
@@ -537,15 +1494,246 @@ impl Viewfinder {
             self.notify_is_recording();
         };
 
+        imp.recording_location
+            .replace(Some(location.as_ref().to_owned()));
+        imp.recording_segment.set(1);
+
+        // The microphone is used by camerabin from now on.
+        self.update_audio_monitor();
+        self.start_segment(location.as_ref());
+
+        let source = glib::timeout_add_local(
+            RECORDING_LIMITS_INTERVAL,
+            glib::clone!(@weak self as obj => @default-return glib::ControlFlow::Break, move || {
+                obj.check_recording_limits();
+                glib::ControlFlow::Continue
+            }),
+        );
+        if let Some(old_source) = imp.recording_limits_source.replace(Some(source)) {
+            old_source.remove();
+        }
+
+        Ok(())
+    }
+
+    /// Starts a loop recording.
+    ///
+    /// The recording is written to files of
+    /// [`loop-segment-duration`](#loop-segment-duration) seconds, numbered
+    /// after `location`. The oldest files are deleted, so that only the last
+    /// [`loop-duration`](#loop-duration) seconds are kept, until they are
+    /// saved with [`fn@Viewfinder::save_loop_recording`]. The footage kept
+    /// when the recording stops is saved as well.
+    ///
+    /// This method throws the same errors as
+    /// [`fn@Viewfinder::start_recording`].
+    pub fn start_loop_recording<P: AsRef<Path>>(
+        &self,
+        location: P,
+    ) -> Result<(), crate::CaptureError> {
+        let imp = self.imp();
+
+        // Set before starting, so that it is known when `is-recording`
+        // changes.
+        imp.is_loop_recording.set(true);
+        if let Err(err) = self.start_recording(location) {
+            imp.is_loop_recording.set(false);
+            return Err(err);
+        }
+        self.notify_is_loop_recording();
+
+        Ok(())
+    }
+
+    /// Saves the footage kept by the loop recording in progress.
+    ///
+    /// The current file is finished, and the
+    /// [`recording-done`](#recording-done) signal is emitted for it and for
+    /// each of the files kept before it, which are not deleted anymore. The
+    /// recording continues in a new file.
+    ///
+    /// This method throws an error if there is no loop recording in progress,
+    /// or if it is being stopped.
+    pub fn save_loop_recording(&self) -> Result<(), crate::CaptureError> {
+        let imp = self.imp();
+
+        if !self.is_recording() || !self.is_loop_recording() {
+            return Err(crate::CaptureError::NoLoopRecording);
+        }
+
+        if imp.is_stopping_recording.get() {
+            return Err(crate::CaptureError::StopRecordingInProgress);
+        }
+
+        imp.is_saving_loop.set(true);
+        imp.is_splitting_recording.set(true);
+        if let Err(err) = self.stop_recording() {
+            imp.is_saving_loop.set(false);
+            imp.is_splitting_recording.set(false);
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    fn start_segment(&self, location: &Path) {
+        let imp = self.imp();
+
         let camerabin = imp.camerabin();
         camerabin.set_property_from_str("mode", "mode-video");
-        camerabin.set_property("location", location.as_ref().display().to_string());
+        camerabin.set_property("location", location.display().to_string());
 
         self.set_tags();
+        let interval = self.timelapse_interval();
+        imp.recording_pause
+            .attach(self.recorded_pads(), interval == 0);
+        if interval > 0 {
+            let pad = camerabin
+                .property::<Option<gst::Element>>("camera-source")
+                .and_then(|source| source.static_pad("vidsrc"));
+            if let Some(pad) = pad {
+                imp.timelapse.attach(
+                    pad,
+                    gst::ClockTime::from_mseconds(interval.into()),
+                    self.timelapse_framerate(),
+                );
+            }
+        }
+        imp.segment_started_at.set(camerabin.current_running_time());
 
         camerabin.emit_by_name::<()>("start-capture", &[]);
+    }
 
-        Ok(())
+    // Continues the recording in the next segment, once the current one is
+    // saved.
+    fn start_next_segment(&self) {
+        let imp = self.imp();
+
+        let Some(base) = imp.recording_location.borrow().clone() else {
+            return;
+        };
+        let segment = imp.recording_segment.get() + 1;
+        imp.recording_segment.set(segment);
+
+        let location = crate::utils::segment_location(&base, segment);
+        let previous = imp.is_recording_video.replace(Some(location.clone()));
+        self.start_segment(&location);
+
+        let Some(previous) = previous else {
+            return;
+        };
+        if self.is_loop_recording() {
+            imp.loop_segments.borrow_mut().push_back(previous);
+            if imp.is_saving_loop.replace(false) {
+                self.save_loop_segments();
+            } else {
+                self.prune_loop_segments();
+            }
+        } else {
+            let file = gio::File::for_path(previous);
+            self.emit_recording_done(Some(&file));
+        }
+    }
+
+    // Deletes the oldest segments of the loop recording that are not needed
+    // to cover `loop-duration`.
+    fn prune_loop_segments(&self) {
+        let imp = self.imp();
+
+        let kept = self
+            .loop_duration()
+            .div_ceil(self.loop_segment_duration().max(1))
+            .max(1) as usize;
+        let mut segments = imp.loop_segments.borrow_mut();
+        while segments.len() > kept {
+            let Some(segment) = segments.pop_front() else {
+                break;
+            };
+            log::debug!("Deleting loop segment {}", segment.display());
+            if let Err(err) = std::fs::remove_file(&segment) {
+                log::warn!("Could not delete {}: {err}", segment.display());
+            }
+        }
+    }
+
+    fn save_loop_segments(&self) {
+        let segments = self.imp().loop_segments.take();
+        for segment in segments {
+            let file = gio::File::for_path(segment);
+            self.emit_recording_done(Some(&file));
+        }
+    }
+
+    fn finish_loop_recording(&self) {
+        let imp = self.imp();
+
+        self.save_loop_segments();
+        imp.is_saving_loop.set(false);
+        if imp.is_loop_recording.replace(false) {
+            self.notify_is_loop_recording();
+        }
+    }
+
+    fn check_recording_limits(&self) {
+        let imp = self.imp();
+
+        if imp.is_stopping_recording.get() || self.is_recording_paused() {
+            return;
+        }
+        let Some(location) = imp.is_recording_video.borrow().clone() else {
+            return;
+        };
+
+        let is_loop = self.is_loop_recording();
+        let max_duration = if is_loop {
+            self.loop_segment_duration()
+        } else {
+            self.max_recording_duration()
+        };
+        let max_size = self.max_recording_size();
+        let is_duration_reached = max_duration > 0
+            && self
+                .segment_duration()
+                .is_some_and(|duration| duration.seconds() >= max_duration as u64);
+        let is_size_reached = max_size > 0
+            && std::fs::metadata(&location).is_ok_and(|metadata| metadata.len() >= max_size);
+        if !is_duration_reached && !is_size_reached {
+            return;
+        }
+
+        log::debug!("Recording limit reached for {}", location.display());
+        let is_splitting = is_loop
+            || matches!(
+                self.recording_limit_action(),
+                crate::RecordingLimitAction::Split
+            );
+        imp.is_splitting_recording.set(is_splitting);
+        if let Err(err) = self.stop_recording() {
+            imp.is_splitting_recording.set(false);
+            log::error!("Could not stop recording: {err}");
+        }
+    }
+
+    // The recorded duration of the current segment.
+    fn segment_duration(&self) -> Option<gst::ClockTime> {
+        let imp = self.imp();
+
+        let now = imp.camerabin().current_running_time();
+        let started_at = imp.segment_started_at.get()?;
+        let paused = imp.recording_pause.paused_duration(now);
+
+        now?.checked_sub(started_at)?.checked_sub(paused)
+    }
+
+    fn stop_recording_limits(&self) {
+        let imp = self.imp();
+
+        if let Some(source) = imp.recording_limits_source.take() {
+            source.remove();
+        }
+        imp.is_splitting_recording.set(false);
+        imp.segment_started_at.set(None);
+        imp.recording_location.take();
     }
 
     /// Stop recording video.
@@ -564,23 +1752,96 @@ impl Viewfinder {
             return Err(crate::CaptureError::NoRecordingToStop);
         }
 
+        if imp.is_stopping_recording.get() {
+            // The recording is being split, it ends with the current segment.
+            if imp.is_splitting_recording.replace(false) {
+                return Ok(());
+            }
+            return Err(crate::CaptureError::StopRecordingInProgress);
+        }
+
+        imp.is_stopping_recording.set(true);
+
+        imp.camerabin().emit_by_name::<()>("stop-capture", &[]);
+
+        Ok(())
+    }
+
+    /// Pauses the recording in progress.
+    ///
+    /// Nothing is recorded until [`fn@Viewfinder::resume_recording`] is
+    /// called, the recording is still saved as a single file.
+    ///
+    /// This method throws an error if there is no recording in progress, or if
+    /// it is already paused or being stopped.
+    pub fn pause_recording(&self) -> Result<(), crate::CaptureError> {
+        let imp = self.imp();
+
+        if !self.is_recording() || self.is_recording_paused() {
+            return Err(crate::CaptureError::NoRecordingToPause);
+        }
+
         if imp.is_stopping_recording.get() {
             return Err(crate::CaptureError::StopRecordingInProgress);
         }
 
-        imp.is_stopping_recording.set(true);
+        imp.recording_pause
+            .pause(imp.camerabin().current_running_time());
+        self.notify_is_recording_paused();
 
-        imp.camerabin().emit_by_name::<()>("stop-capture", &[]);
+        Ok(())
+    }
+
+    /// Resumes the recording paused with [`fn@Viewfinder::pause_recording`].
+    ///
+    /// This method throws an error if there is no paused recording.
+    pub fn resume_recording(&self) -> Result<(), crate::CaptureError> {
+        let imp = self.imp();
+
+        if !self.is_recording() || !self.is_recording_paused() {
+            return Err(crate::CaptureError::NoRecordingToResume);
+        }
+
+        imp.recording_pause
+            .resume(imp.camerabin().current_running_time());
+        self.notify_is_recording_paused();
 
         Ok(())
     }
 
-    pub fn connect_picture_done<F: Fn(&Self, Option<&gio::File>) + 'static>(&self, f: F) {
+    // The pads through which the recorded video and audio flow.
+    fn recorded_pads(&self) -> Vec<gst::Pad> {
+        let camerabin = self.imp().camerabin();
+
+        let video_pad = camerabin
+            .property::<Option<gst::Element>>("camera-source")
+            .and_then(|source| source.static_pad("vidsrc"));
+        let audio_pad = camerabin
+            .property::<Option<gst::Element>>("audio-source")
+            .and_then(|source| source.static_pad("src"));
+
+        video_pad.into_iter().chain(audio_pad).collect()
+    }
+
+    fn reset_recording_pause(&self) {
+        let was_paused = self.is_recording_paused();
+        self.imp().recording_pause.detach();
+        if was_paused {
+            self.notify_is_recording_paused();
+        }
+    }
+
+    pub fn connect_picture_done<
+        F: Fn(&Self, Option<&gio::File>, Option<&crate::CameraMode>) + 'static,
+    >(
+        &self,
+        f: F,
+    ) {
         self.connect_closure(
             "picture-done",
             false,
-            glib::closure_local!(|obj, file| {
-                f(obj, file);
+            glib::closure_local!(|obj, file, mode| {
+                f(obj, file, mode);
             }),
         );
     }
@@ -605,6 +1866,36 @@ impl Viewfinder {
         );
     }
 
+    pub fn connect_audio_level<F: Fn(&Self, &crate::AudioLevel) + 'static>(&self, f: F) {
+        self.connect_closure(
+            "audio-level",
+            false,
+            glib::closure_local!(|obj, level| {
+                f(obj, level);
+            }),
+        );
+    }
+
+    pub fn connect_burst_progress<F: Fn(&Self, u32, &gio::File) + 'static>(&self, f: F) {
+        self.connect_closure(
+            "burst-progress",
+            false,
+            glib::closure_local!(|obj, index, file| {
+                f(obj, index, file);
+            }),
+        );
+    }
+
+    pub fn connect_burst_done<F: Fn(&Self, &gio::ListModel) + 'static>(&self, f: F) {
+        self.connect_closure(
+            "burst-done",
+            false,
+            glib::closure_local!(|obj, files| {
+                f(obj, files);
+            }),
+        );
+    }
+
     /// Starts the viewfinder.
     pub fn start_stream(&self) {
         glib::spawn_future_local(glib::clone!(@weak self as obj => async move {
@@ -704,25 +1995,111 @@ impl Viewfinder {
 
                     self.on_barcode_detected(data_type, data);
                 }
+                Some(s) if s.has_name("level") => self.on_level(s),
+                Some(s) if s.has_name("GstMultiFileSink") => {
+                    self.on_burst_file(msg.src(), s);
+                }
                 _ => (),
             },
             _ => (),
         }
     }
 
+    fn on_audio_monitor_message(&self, msg: &gst::Message) {
+        match msg.view() {
+            gst::MessageView::Error(err) => {
+                log::warn!("Could not monitor the audio level: {}", err.error());
+            }
+            gst::MessageView::Element(msg) => {
+                if let Some(s) = msg.structure().filter(|s| s.has_name("level")) {
+                    self.on_level(s);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_level(&self, structure: &gst::StructureRef) {
+        if let Some(level) = crate::AudioLevel::from_structure(structure) {
+            self.emit_audio_level(&level);
+        }
+    }
+
+    fn on_burst_file(&self, src: Option<&gst::Object>, structure: &gst::StructureRef) {
+        let imp = self.imp();
+
+        let still_file = imp
+            .still
+            .borrow()
+            .as_ref()
+            .and_then(|still| still.add_file(src, structure));
+        if let Some((_, file)) = still_file {
+            self.finish_still();
+            self.on_image_done(&file);
+            return;
+        }
+
+        let (progress, is_done) = match imp.burst.borrow().as_ref() {
+            Some(burst) => (burst.add_file(src, structure), burst.is_done()),
+            None => return,
+        };
+        if let Some((index, file)) = progress {
+            self.emit_burst_progress(index, &file);
+        }
+        if is_done {
+            self.finish_burst();
+        }
+    }
+
+    fn finish_still(&self) {
+        if let Some(still) = self.imp().still.take() {
+            self.imp().tee.get().unwrap().remove_branch(still.bin());
+        }
+    }
+
+    fn finish_burst(&self) {
+        let imp = self.imp();
+
+        let Some(burst) = imp.burst.take() else {
+            return;
+        };
+        imp.tee.get().unwrap().remove_branch(burst.bin());
+        self.emit_burst_done(burst.files().upcast_ref());
+    }
+
     fn on_image_done(&self, file: &gio::File) {
-        self.imp().is_taking_picture.set(false);
+        let imp = self.imp();
+        imp.is_taking_picture.set(false);
+        let mode = imp.capture_mode.take();
 
-        self.emit_picture_done(Some(file));
+        self.emit_picture_done(Some(file), mode.as_ref());
     }
 
     fn on_video_done(&self) {
-        self.imp().is_stopping_recording.set(false);
+        let imp = self.imp();
+        imp.is_stopping_recording.set(false);
 
-        if let Some(path) = self.imp().is_recording_video.take() {
+        if imp.is_splitting_recording.replace(false) && self.is_recording() {
+            self.start_next_segment();
+            return;
+        }
+
+        if let Some(path) = imp.is_recording_video.take() {
+            self.stop_recording_limits();
+            self.reset_recording_pause();
+            imp.timelapse.detach();
             self.notify_is_recording();
+            self.update_audio_monitor();
+            self.finish_loop_recording();
             let file = gio::File::for_path(path);
             self.emit_recording_done(Some(&file));
+            self.apply_pending_timelapse_interval();
+        }
+    }
+
+    fn apply_pending_timelapse_interval(&self) {
+        if let Some(interval) = self.imp().pending_timelapse_interval.take() {
+            self.set_timelapse_interval(interval);
         }
     }
 
@@ -759,17 +2136,26 @@ impl Viewfinder {
         let imp = self.imp();
 
         if imp.is_taking_picture.replace(false) {
-            self.emit_picture_done(None);
+            self.finish_still();
+            imp.capture_mode.take();
+            self.emit_picture_done(None, None);
         }
         if imp.is_recording_video.replace(None).is_some() {
+            self.stop_recording_limits();
+            self.reset_recording_pause();
+            imp.timelapse.detach();
             self.notify_is_recording();
+            self.update_audio_monitor();
+            self.finish_loop_recording();
             self.emit_recording_done(None);
+            self.apply_pending_timelapse_interval();
         }
         imp.is_stopping_recording.set(false);
+        self.finish_burst();
     }
 
-    fn emit_picture_done(&self, file: Option<&gio::File>) {
-        self.emit_by_name::<()>("picture-done", &[&file]);
+    fn emit_picture_done(&self, file: Option<&gio::File>, mode: Option<&crate::CameraMode>) {
+        self.emit_by_name::<()>("picture-done", &[&file, &mode]);
     }
 
     fn emit_recording_done(&self, file: Option<&gio::File>) {
@@ -780,6 +2166,18 @@ impl Viewfinder {
         self.emit_by_name::<()>("code-detected", &[&data_type, &data]);
     }
 
+    fn emit_audio_level(&self, level: &crate::AudioLevel) {
+        self.emit_by_name::<()>("audio-level", &[level]);
+    }
+
+    fn emit_burst_progress(&self, index: u32, file: &gio::File) {
+        self.emit_by_name::<()>("burst-progress", &[&index, file]);
+    }
+
+    fn emit_burst_done(&self, files: &gio::ListModel) {
+        self.emit_by_name::<()>("burst-done", &[files]);
+    }
+
     fn set_tags(&self) {
         let imp = self.imp();
 
@@ -787,8 +2185,20 @@ impl Viewfinder {
             .camerabin()
             .dynamic_cast_ref::<gst::TagSetter>()
             .unwrap();
-        tagsetter.add_tag::<gst::tags::ApplicationName>(
+        tagsetter.reset_tags();
+
+        if self.strip_metadata() {
+            return;
+        }
+
+        // This is written as the EXIF Software tag.
+        let application_name = format!(
+            "{} (Aperture {})",
             crate::APP_ID.get().unwrap(),
+            crate::VERSION
+        );
+        tagsetter.add_tag::<gst::tags::ApplicationName>(
+            &application_name.as_str(),
             gst::TagMergeMode::Replace,
         );
 
@@ -796,44 +2206,247 @@ impl Viewfinder {
             tagsetter.add_tag::<gst::tags::DateTime>(&datetime, gst::TagMergeMode::Replace);
         }
         if let Some(camera) = self.camera() {
-            let device_model = camera.display_name();
+            let device_model = camera
+                .nick()
+                .unwrap_or_else(|| camera.display_name().to_string());
             tagsetter.add_tag::<gst::tags::DeviceModel>(
                 &device_model.as_str(),
                 gst::TagMergeMode::Replace,
             );
+
+            if let Some(manufacturer) = camera.manufacturer() {
+                tagsetter.add_tag::<gst::tags::DeviceManufacturer>(
+                    &manufacturer.as_str(),
+                    gst::TagMergeMode::Replace,
+                );
+            }
+
+            add_exposure_tags(tagsetter, &camera);
+        }
+
+        // The camera feed is already rotated by the videoflip element.
+        tagsetter.add_tag::<gst::tags::ImageOrientation>(&"rotate-0", gst::TagMergeMode::Replace);
+
+        if let Some(geo_location) = self.geo_location() {
+            geo_location.add_tags(tagsetter);
+        }
+    }
+
+    /// Sets a custom encoding profile for recording videos.
+    ///
+    /// This overrides the [`video-format`](#video-format), passing `None`
+    /// goes back to using it. This method throws an error if there is a
+    /// recording in progress. The camera feed is restarted.
+    pub fn set_video_profile(
+        &self,
+        profile: Option<&gst_pbutils::EncodingContainerProfile>,
+    ) -> Result<(), crate::CaptureError> {
+        let imp = self.imp();
+
+        if imp.is_recording_video.borrow().is_some() {
+            return Err(crate::CaptureError::RecordingInProgress);
+        }
+
+        imp.custom_video_profile.replace(profile.cloned());
+        self.setup_recording();
+
+        Ok(())
+    }
+
+    /// Calls `f`, which changes several settings of the viewfinder, e.g. the
+    /// [`picture-quality`](#picture-quality) and the bitrates, restarting the
+    /// camera feed once rather than once per setting.
+    pub fn reconfigure(&self, f: impl FnOnce(&Self)) {
+        let imp = self.imp();
+
+        // Nested calls are part of the outer one.
+        if imp.pending_restart.get().is_some() {
+            f(self);
+            return;
         }
+
+        imp.pending_restart.set(Some(false));
+        f(self);
+        let pending_restart = imp.pending_restart.take().unwrap_or_default();
+
+        self.restart_stream(pending_restart);
+    }
+
+    /// Gets the encoding profile used for recording videos.
+    ///
+    /// # Returns
+    ///
+    /// the profile set with [`fn@Viewfinder::set_video_profile`], or the one
+    /// of the current [`video-format`](#video-format).
+    pub fn video_profile(&self) -> gst_pbutils::EncodingContainerProfile {
+        self.imp()
+            .custom_video_profile
+            .borrow()
+            .clone()
+            .unwrap_or_else(|| {
+                video_profile(
+                    self.video_format(),
+                    self.video_bitrate(),
+                    self.audio_bitrate(),
+                    self.records_audio(),
+                )
+            })
     }
 
     fn setup_recording(&self) {
-        use gst_pbutils::encoding_profile::EncodingProfileBuilder;
-
-        let video_profile =
-            gst_pbutils::EncodingVideoProfile::builder(&gst::Caps::builder("video/x-vp8").build())
-                .preset("Profile Realtime")
-                .variable_framerate(true)
-                .build();
-        let audio_profile = gst_pbutils::EncodingAudioProfile::builder(
-            &gst::Caps::builder("audio/x-vorbis").build(),
-        )
-        .build();
-        let profiles = gst_pbutils::EncodingContainerProfile::builder(
-            &gst::Caps::builder("video/webm").build(),
-        )
-        .name("WebM audio/video")
-        .description("Standard WebM/VP8/Vorbis")
-        .add_profile(video_profile)
-        .add_profile(audio_profile)
-        .build();
+        // Custom profiles might have an audio stream regardless.
+        self.imp()
+            .camerabin()
+            .set_property("mute", !self.records_audio());
+        self.set_encoding_profile("video-profile", &self.video_profile());
+    }
 
-        let camerabin = self.imp().camerabin();
+    // Whether recordings have an audio stream.
+    fn records_audio(&self) -> bool {
+        self.record_audio() && self.timelapse_interval() == 0
+    }
+
+    fn setup_audio_source(&self) {
+        let audio_source = self
+            .create_audio_source()
+            .inspect_err(|err| log::error!("Could not create audio source: {err}"))
+            .ok();
+        // camerabin falls back to autoaudiosrc when unset.
+        self.reconfigure_camerabin(|camerabin| {
+            camerabin.set_property("audio-source", &audio_source);
+        });
+    }
 
-        camerabin.set_property("video-profile", profiles);
+    // Creates a source for the selected microphone, posting level messages.
+    fn create_audio_source(&self) -> Result<gst::Element, glib::BoolError> {
+        let imp = self.imp();
+
+        let source = match self
+            .microphone()
+            .or_else(|| imp.devices.get().unwrap().default_microphone())
+        {
+            Some(microphone) => microphone.create_element()?,
+            None => gst::ElementFactory::make("autoaudiosrc").build()?,
+        };
+        let level = gst::ElementFactory::make("level")
+            .property("post-messages", true)
+            .property("interval", LEVEL_INTERVAL)
+            .build()?;
+
+        let bin = gst::Bin::new();
+        bin.add_many([&source, &level])?;
+        source.link(&level)?;
+
+        let ghost_pad = gst::GhostPad::with_target(&level.static_pad("src").unwrap())?;
+        ghost_pad.set_active(true)?;
+        bin.add_pad(&ghost_pad)?;
+
+        Ok(bin.upcast())
+    }
+
+    // While recording the audio level is measured by the audio source of
+    // camerabin, otherwise by a separate pipeline if monitoring.
+    fn update_audio_monitor(&self) {
+        let imp = self.imp();
+
+        if let Some((pipeline, _)) = imp.audio_monitor.take() {
+            if let Err(err) = pipeline.set_state(gst::State::Null) {
+                log::error!("Could not stop the audio monitor: {err}");
+            }
+        }
+
+        if !self.monitor_audio() || !self.records_audio() || self.is_recording() {
+            return;
+        }
+
+        match self.create_audio_monitor() {
+            Ok(monitor) => {
+                imp.audio_monitor.replace(Some(monitor));
+            }
+            Err(err) => log::error!("Could not start the audio monitor: {err}"),
+        }
+    }
+
+    fn create_audio_monitor(
+        &self,
+    ) -> Result<(gst::Pipeline, gst::bus::BusWatchGuard), glib::BoolError> {
+        let pipeline = gst::Pipeline::new();
+        let source = self.create_audio_source()?;
+        let sink = gst::ElementFactory::make("fakesink")
+            .property("sync", false)
+            .build()?;
+        pipeline.add_many([&source, &sink])?;
+        source.link(&sink)?;
+
+        let watch = pipeline.bus().unwrap().add_watch_local(
+            glib::clone!(@weak self as obj => @default-return glib::ControlFlow::Break, move |_, msg| {
+                obj.on_audio_monitor_message(msg);
+                glib::ControlFlow::Continue
+            }),
+        )?;
+
+        pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|err| glib::bool_error!("{err}"))?;
+
+        Ok((pipeline, watch))
+    }
+
+    fn setup_picture_profile(&self) {
+        let profile = picture_profile(
+            self.picture_format(),
+            self.picture_quality(),
+            self.strip_metadata(),
+        );
+        self.set_encoding_profile("image-profile", &profile);
+    }
+
+    fn set_encoding_profile(
+        &self,
+        property: &str,
+        profile: &impl IsA<gst_pbutils::EncodingProfile>,
+    ) {
+        self.reconfigure_camerabin(|camerabin| {
+            camerabin.set_property(property, profile.as_ref());
+        });
+    }
+
+    // Encoding profiles and the audio source are only applied when camerabin
+    // goes from NULL to READY, so the stream is restarted if running.
+    fn reconfigure_camerabin(&self, f: impl FnOnce(&gst::Element)) {
+        let imp = self.imp();
+        let camerabin = imp.camerabin();
+
+        let is_running = matches!(
+            camerabin.current_state(),
+            gst::State::Playing | gst::State::Paused
+        );
+        if is_running {
+            self.stop_stream();
+        }
+
+        f(camerabin);
+
+        if let Some(pending_restart) = imp.pending_restart.get() {
+            imp.pending_restart.set(Some(pending_restart || is_running));
+            return;
+        }
+
+        self.restart_stream(is_running);
+    }
+
+    fn restart_stream(&self, was_running: bool) {
+        if was_running && self.is_realized() && matches!(self.state(), ViewfinderState::Ready) {
+            self.start_stream();
+        }
     }
 
     fn init(&self) {
         let imp = self.imp();
         let devices = imp.devices.get().unwrap();
 
+        self.setup_audio_source();
+
         if let Some(camera) = devices.default_camera().or_else(|| devices.camera(0)) {
             if matches!(
                 self.state(),
@@ -854,6 +2467,19 @@ impl Viewfinder {
         );
     }
 
+    /// Applies the effect and the adjustments to the camera feed.
+    fn update_color_balance(&self) {
+        let effect = self.effect().balance();
+        let balance = crate::color_balance::Balance {
+            brightness: effect.brightness + self.brightness(),
+            contrast: effect.contrast * self.contrast(),
+            saturation: effect.saturation * self.saturation(),
+            hue: effect.hue + self.hue(),
+            tint: effect.tint + (self.color_temperature() * COLOR_TEMPERATURE_TINT) as i32,
+        };
+        self.imp().color_balance.set(balance);
+    }
+
     fn create_camera_element(
         &self,
         device_src: &gst::Element,
@@ -869,10 +2495,19 @@ impl Viewfinder {
             .property_from_str("video-direction", "auto")
             .build()?;
 
-        bin.add_many([device_src, &capsfilter, &decodebin3, &videoflip])?;
+        let color_balance = self.imp().color_balance.create_bin()?;
+
+        bin.add_many([
+            device_src,
+            &capsfilter,
+            &decodebin3,
+            &videoflip,
+            &color_balance,
+        ])?;
         gst::Element::link_many([device_src, &capsfilter, &decodebin3])?;
+        videoflip.link(&color_balance)?;
 
-        self.imp().capsfilter.set(capsfilter).unwrap();
+        self.imp().capsfilter.replace(Some(capsfilter));
 
         decodebin3.connect_pad_added(glib::clone!(@weak videoflip => move |_, pad| {
             if pad.stream().is_some_and(|stream| matches!(stream.stream_type(), gst::StreamType::VIDEO)) {
@@ -881,7 +2516,10 @@ impl Viewfinder {
             }
         }));
 
-        let pad = videoflip.static_pad("src").unwrap();
+        // The source feeds the viewfinder, pictures and recordings alike, so
+        // that they are all processed.
+        let pad = color_balance.static_pad("src").unwrap();
+        self.imp().frame_processors.attach(&pad);
         let ghost_pad = gst::GhostPad::with_target(&pad)?;
         ghost_pad.set_active(true)?;
 
@@ -898,29 +2536,331 @@ impl Viewfinder {
     fn setup_camera_element(&self, camera: &crate::Camera) -> Result<(), glib::BoolError> {
         let imp = self.imp();
 
-        if let Some(element) = imp.camera_element.get() {
-            camera.reconfigure(element)?;
-        } else {
-            let element = camera.create_element()?;
+        let reconfigured = imp
+            .camera_element
+            .borrow()
+            .as_ref()
+            .map(|element| camera.reconfigure(element));
+        match reconfigured {
+            Some(Ok(())) => (),
+            // Not every device can reconfigure elements created by other
+            // devices, in that case we create a new camera source.
+            Some(Err(err)) => {
+                log::debug!("Could not reconfigure camera element, creating a new one: {err}");
+                self.create_camera_source(camera)?;
+            }
+            None => self.create_camera_source(camera)?,
+        }
 
-            let wrapper = self.create_camera_element(&element)?;
-            imp.camerabin().set_property("camera-source", &wrapper);
+        self.update_caps();
 
-            imp.camera_element.set(element).unwrap();
-        }
+        let is_front_camera = !matches!(camera.location(), crate::CameraLocation::Back);
+        imp.is_front_camera.set(is_front_camera);
+
+        Ok(())
+    }
 
-        if let Some(capsfilter) = imp.capsfilter.get() {
-            let caps = camera.best_caps();
+    /// Sets the caps of the camera source from the current mode, or the best
+    /// caps of the camera if there is none.
+    ///
+    /// If there is a picture mode, the camera source also allows its caps and
+    /// camerabin switches to them while taking a picture.
+    fn update_caps(&self) {
+        let imp = self.imp();
+        let camerabin = imp.camerabin();
+
+        let viewfinder_caps = match (self.mode(), self.camera()) {
+            (Some(mode), _) => mode.caps(),
+            (None, Some(camera)) => camera.best_caps(),
+            (None, None) => return,
+        };
+
+        let caps = if let Some(picture_mode) = self.picture_mode() {
+            let picture_caps = picture_mode.caps();
+            let mut caps = viewfinder_caps.clone();
+            caps.merge(picture_caps.clone());
+
+            camerabin.set_property("viewfinder-caps", &viewfinder_caps);
+            camerabin.set_property("image-capture-caps", &picture_caps);
+
+            caps
+        } else {
+            camerabin.set_property("viewfinder-caps", gst::Caps::new_any());
+            camerabin.set_property("image-capture-caps", gst::Caps::new_any());
+
+            viewfinder_caps
+        };
+        log::debug!("Setting camera caps: {caps:#?}");
+
+        if let Some(capsfilter) = imp.capsfilter.borrow().as_ref() {
             capsfilter.set_property("caps", &caps);
         }
+    }
 
-        let is_front_camera = !matches!(camera.location(), crate::CameraLocation::Back);
-        imp.is_front_camera.set(is_front_camera);
+    /// Gets the mode negotiated by the camera source.
+    fn negotiated_mode(&self) -> Option<crate::CameraMode> {
+        let capsfilter = self.imp().capsfilter.borrow().clone()?;
+        let caps = capsfilter.static_pad("src")?.current_caps()?;
+
+        crate::utils::caps::modes(&caps).into_iter().next()
+    }
+
+    fn create_camera_source(&self, camera: &crate::Camera) -> Result<(), glib::BoolError> {
+        let imp = self.imp();
+
+        let element = camera.create_element()?;
+
+        let wrapper = self.create_camera_element(&element)?;
+        imp.camerabin().set_property("camera-source", &wrapper);
+
+        imp.camera_element.replace(Some(element));
 
         Ok(())
     }
 }
 
+fn video_profile(
+    format: crate::VideoFormat,
+    video_bitrate: u32,
+    audio_bitrate: u32,
+    record_audio: bool,
+) -> gst_pbutils::EncodingContainerProfile {
+    use gst_pbutils::encoding_profile::EncodingProfileBuilder;
+
+    let video_caps = format.video_caps();
+    let mut video_profile =
+        gst_pbutils::EncodingVideoProfile::builder(&video_caps).variable_framerate(true);
+    if matches!(format, crate::VideoFormat::Vp8Webm) {
+        video_profile = video_profile.preset("Profile Realtime");
+    }
+    video_profile = video_profile.element_properties(video_encoder_properties(video_bitrate));
+
+    let audio_caps = format.audio_caps();
+    let mut audio_profile = gst_pbutils::EncodingAudioProfile::builder(&audio_caps);
+    if audio_bitrate > 0 {
+        audio_profile = audio_profile.element_properties(audio_bitrate_properties(audio_bitrate));
+    }
+
+    let (name, description) = match format {
+        crate::VideoFormat::Vp8Webm => ("WebM audio/video", "Standard WebM/VP8/Vorbis"),
+        crate::VideoFormat::Vp9Webm => ("WebM audio/video", "Standard WebM/VP9/Opus"),
+        crate::VideoFormat::Av1Webm => ("WebM audio/video", "Standard WebM/AV1/Opus"),
+        crate::VideoFormat::H264Mp4 => ("MP4 audio/video", "Standard MP4/H.264/AAC"),
+        crate::VideoFormat::H264Matroska => ("Matroska audio/video", "Standard MKV/H.264/Opus"),
+    };
+
+    let mut profile = gst_pbutils::EncodingContainerProfile::builder(&format.container_caps())
+        .name(name)
+        .description(description)
+        .add_profile(video_profile.build());
+    // camerabin does not record audio if the profile has no audio stream.
+    if record_audio {
+        profile = profile.add_profile(audio_profile.build());
+    }
+
+    profile.build()
+}
+
+/// Adds the exposure settings reported by the controls of `camera` to
+/// `tagsetter`.
+fn add_exposure_tags(tagsetter: &gst::TagSetter, camera: &crate::Camera) {
+    use crate::CameraControlId;
+
+    if let Some(auto_exposure) = camera.control(CameraControlId::AutoExposure) {
+        // V4L2_EXPOSURE_MANUAL
+        let mode = if auto_exposure.value() == 1 {
+            "manual-exposure"
+        } else {
+            "auto-exposure"
+        };
+        tagsetter.add_tag::<gst::tags::CapturingExposureMode>(&mode, gst::TagMergeMode::Replace);
+    }
+
+    // The exposure time is stale while the exposure is automatic.
+    if let Some(exposure_time) = camera
+        .control(CameraControlId::ExposureTime)
+        .filter(|control| !control.is_inactive() && control.value() > 0)
+    {
+        // In units of 100 µs.
+        let shutter_speed = gst::Fraction::new(exposure_time.value(), 10_000);
+        tagsetter.add_tag::<gst::tags::CapturingShutterSpeed>(
+            &shutter_speed,
+            gst::TagMergeMode::Replace,
+        );
+    }
+
+    if let Some(auto_white_balance) = camera.control(CameraControlId::AutoWhiteBalance) {
+        let mode = if auto_white_balance.value() == 0 {
+            "manual"
+        } else {
+            "auto"
+        };
+        tagsetter.add_tag::<gst::tags::CapturingWhiteBalance>(&mode, gst::TagMergeMode::Replace);
+    }
+}
+
+/// The properties keeping the supported video encoders up with the camera
+/// feed, and setting a bitrate of `kbps` unless it is `0`. Encoders do not
+/// agree on units. VP8 is made fast enough by its `Profile Realtime` preset.
+fn video_encoder_properties(kbps: u32) -> gst_pbutils::ElementProperties {
+    use gst_pbutils::ElementPropertiesMapItem as Item;
+
+    let has_bitrate = kbps > 0;
+    let bps = kbps.saturating_mul(1000);
+    // Most encoders take a signed bitrate.
+    let bps_i32 = i32::try_from(bps).unwrap_or(i32::MAX);
+
+    let vp8enc = Item::builder("vp8enc");
+    let vp8enc = if has_bitrate {
+        vp8enc.field("target-bitrate", bps_i32)
+    } else {
+        vp8enc
+    };
+
+    // A deadline of 1µs selects the realtime mode of libvpx.
+    let vp9enc = Item::builder("vp9enc")
+        .field("deadline", 1i64)
+        .field("cpu-used", 8i32)
+        .field("row-mt", true);
+    let vp9enc = if has_bitrate {
+        vp9enc.field("target-bitrate", bps_i32)
+    } else {
+        vp9enc
+    };
+
+    // Enumerations are set by their nick, as in serialized profiles.
+    let av1enc = Item::builder("av1enc")
+        .field("usage-profile", "realtime")
+        .field("cpu-used", 6i32)
+        .field("row-mt", true);
+    let av1enc = if has_bitrate {
+        av1enc.field("target-bitrate", kbps)
+    } else {
+        av1enc
+    };
+
+    let rav1enc = Item::builder("rav1enc")
+        .field("speed-preset", 10u32)
+        .field("low-latency", true);
+    let rav1enc = if has_bitrate {
+        rav1enc.field("bitrate", bps_i32)
+    } else {
+        rav1enc
+    };
+
+    let svtav1enc = Item::builder("svtav1enc").field("preset", 10u32);
+    let svtav1enc = if has_bitrate {
+        svtav1enc.field("target-bitrate", kbps)
+    } else {
+        svtav1enc
+    };
+
+    let x264enc = Item::builder("x264enc")
+        .field("speed-preset", "veryfast")
+        .field("tune", "zerolatency");
+    let x264enc = if has_bitrate {
+        x264enc.field("bitrate", kbps)
+    } else {
+        x264enc
+    };
+
+    let openh264enc = Item::builder("openh264enc");
+    let openh264enc = if has_bitrate {
+        openh264enc.field("bitrate", bps)
+    } else {
+        openh264enc
+    };
+
+    gst_pbutils::ElementProperties::builder_map()
+        .item(vp8enc.build())
+        .item(vp9enc.build())
+        .item(av1enc.build())
+        .item(rav1enc.build())
+        .item(svtav1enc.build())
+        .item(x264enc.build())
+        .item(openh264enc.build())
+        .build()
+}
+
+/// The properties setting a bitrate of `kbps` in the supported audio encoders.
+fn audio_bitrate_properties(kbps: u32) -> gst_pbutils::ElementProperties {
+    use gst_pbutils::ElementPropertiesMapItem as Item;
+
+    let bps = i32::try_from(kbps.saturating_mul(1000)).unwrap_or(i32::MAX);
+    gst_pbutils::ElementProperties::builder_map()
+        .item(Item::builder("vorbisenc").field("bitrate", bps).build())
+        .item(Item::builder("opusenc").field("bitrate", bps).build())
+        .item(
+            Item::builder("avenc_aac")
+                .field("bitrate", bps as i64)
+                .build(),
+        )
+        .item(Item::builder("fdkaacenc").field("bitrate", bps).build())
+        .item(Item::builder("voaacenc").field("bitrate", bps).build())
+        .build()
+}
+
+/// The properties setting a quality from 1 to 100 in the supported picture
+/// encoders.
+fn picture_quality_properties(quality: u32) -> gst_pbutils::ElementProperties {
+    use gst_pbutils::ElementPropertiesMapItem as Item;
+
+    gst_pbutils::ElementProperties::builder_map()
+        .item(
+            Item::builder("jpegenc")
+                .field("quality", quality as i32)
+                .build(),
+        )
+        .item(
+            Item::builder("webpenc")
+                .field("quality", quality as f32)
+                .build(),
+        )
+        .build()
+}
+
+/// The encoding profile for pictures in `format`.
+///
+/// JPEG pictures are muxed with `jifmux`, which writes their EXIF and XMP
+/// metadata, unless `strip_metadata` is set.
+fn picture_profile(
+    format: crate::PictureFormat,
+    quality: u32,
+    strip_metadata: bool,
+) -> gst_pbutils::EncodingProfile {
+    use gst_pbutils::encoding_profile::EncodingProfileBuilder;
+
+    let encoder_caps = format.encoder_caps();
+    let mut video_profile = gst_pbutils::EncodingVideoProfile::builder(&encoder_caps)
+        .variable_framerate(true)
+        .presence(1);
+    if quality > 0 {
+        video_profile = video_profile.element_properties(picture_quality_properties(quality));
+    }
+    let video_profile = video_profile.build();
+
+    match format {
+        crate::PictureFormat::Avif => {
+            gst_pbutils::EncodingContainerProfile::builder(&format.caps())
+                .name("AVIF")
+                .add_profile(video_profile)
+                .build()
+                .upcast()
+        }
+        crate::PictureFormat::Jpeg
+            if !strip_metadata && gst::ElementFactory::find("jifmux").is_some() =>
+        {
+            gst_pbutils::EncodingContainerProfile::builder(&format.caps())
+                .name("JPEG")
+                .description("JPEG with EXIF and XMP metadata")
+                .add_profile(video_profile)
+                .build()
+                .upcast()
+        }
+        _ => video_profile.upcast(),
+    }
+}
+
 fn create_zbar_bin() -> Result<gst::Element, glib::BoolError> {
     let bin = gst::Bin::new();
 
@@ -11,12 +11,32 @@ use gtk::prelude::*;
 pub enum PictureFormat {
     #[default]
     Jpeg,
+    Png,
+    Webp,
+    Avif,
+    Tiff,
 }
 
 impl PictureFormat {
+    pub const ALL: [Self; 5] = [Self::Jpeg, Self::Png, Self::Webp, Self::Avif, Self::Tiff];
+
     pub fn as_str(&self) -> &str {
         match self {
             Self::Jpeg => "jpeg",
+            Self::Png => "png",
+            Self::Webp => "webp",
+            Self::Avif => "avif",
+            Self::Tiff => "tiff",
+        }
+    }
+
+    pub fn mime_type(&self) -> &str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+            Self::Webp => "image/webp",
+            Self::Avif => "image/avif",
+            Self::Tiff => "image/tiff",
         }
     }
 
@@ -25,6 +45,18 @@ impl PictureFormat {
             // TRANSLATORS This is the image format presented in the preferences
             // window.
             Self::Jpeg => gettext("JPEG"),
+            // TRANSLATORS This is the image format presented in the preferences
+            // window.
+            Self::Png => gettext("PNG"),
+            // TRANSLATORS This is the image format presented in the preferences
+            // window.
+            Self::Webp => gettext("WebP"),
+            // TRANSLATORS This is the image format presented in the preferences
+            // window.
+            Self::Avif => gettext("AVIF"),
+            // TRANSLATORS This is the image format presented in the preferences
+            // window.
+            Self::Tiff => gettext("TIFF"),
         }
     }
 }
@@ -32,12 +64,40 @@ impl PictureFormat {
 impl From<i32> for PictureFormat {
     fn from(value: i32) -> Self {
         match value {
-            1 => Self::Jpeg,
+            0 => Self::Jpeg,
+            1 => Self::Png,
+            2 => Self::Webp,
+            3 => Self::Avif,
+            4 => Self::Tiff,
             _ => Self::default(),
         }
     }
 }
 
+impl From<PictureFormat> for aperture::PictureFormat {
+    fn from(value: PictureFormat) -> Self {
+        match value {
+            PictureFormat::Jpeg => Self::Jpeg,
+            PictureFormat::Png => Self::Png,
+            PictureFormat::Webp => Self::Webp,
+            PictureFormat::Avif => Self::Avif,
+            PictureFormat::Tiff => Self::Tiff,
+        }
+    }
+}
+
+impl From<aperture::PictureFormat> for PictureFormat {
+    fn from(value: aperture::PictureFormat) -> Self {
+        match value {
+            aperture::PictureFormat::Jpeg => Self::Jpeg,
+            aperture::PictureFormat::Png => Self::Png,
+            aperture::PictureFormat::Webp => Self::Webp,
+            aperture::PictureFormat::Avif => Self::Avif,
+            aperture::PictureFormat::Tiff => Self::Tiff,
+        }
+    }
+}
+
 /// Enum representing the org.gnome.Snapshot.VideoFormat enum defined in
 /// the gschema.
 #[derive(Default, Debug, Copy, Clone, PartialEq)]
@@ -45,12 +105,46 @@ impl From<i32> for PictureFormat {
 pub enum VideoFormat {
     #[default]
     Vp8Webm,
+    Vp9Webm,
+    Av1Webm,
+    H264Mp4,
+    H264Matroska,
 }
 
 impl VideoFormat {
+    pub const ALL: [Self; 5] = [
+        Self::Vp8Webm,
+        Self::Vp9Webm,
+        Self::Av1Webm,
+        Self::H264Mp4,
+        Self::H264Matroska,
+    ];
+
     pub fn as_str(&self) -> &str {
         match self {
-            Self::Vp8Webm => "webm",
+            Self::Vp8Webm | Self::Vp9Webm | Self::Av1Webm => "webm",
+            Self::H264Mp4 => "mp4",
+            Self::H264Matroska => "mkv",
+        }
+    }
+
+    pub fn translatable_string(&self) -> String {
+        match self {
+            // TRANSLATORS This is the video format presented in the preferences
+            // window.
+            Self::Vp8Webm => gettext("WebM (VP8)"),
+            // TRANSLATORS This is the video format presented in the preferences
+            // window.
+            Self::Vp9Webm => gettext("WebM (VP9)"),
+            // TRANSLATORS This is the video format presented in the preferences
+            // window.
+            Self::Av1Webm => gettext("WebM (AV1)"),
+            // TRANSLATORS This is the video format presented in the preferences
+            // window.
+            Self::H264Mp4 => gettext("MP4 (H.264)"),
+            // TRANSLATORS This is the video format presented in the preferences
+            // window.
+            Self::H264Matroska => gettext("Matroska (H.264)"),
         }
     }
 }
@@ -59,6 +153,101 @@ impl From<i32> for VideoFormat {
     fn from(value: i32) -> Self {
         match value {
             0 => Self::Vp8Webm,
+            1 => Self::Vp9Webm,
+            2 => Self::Av1Webm,
+            3 => Self::H264Mp4,
+            4 => Self::H264Matroska,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl From<VideoFormat> for aperture::VideoFormat {
+    fn from(value: VideoFormat) -> Self {
+        match value {
+            VideoFormat::Vp8Webm => Self::Vp8Webm,
+            VideoFormat::Vp9Webm => Self::Vp9Webm,
+            VideoFormat::Av1Webm => Self::Av1Webm,
+            VideoFormat::H264Mp4 => Self::H264Mp4,
+            VideoFormat::H264Matroska => Self::H264Matroska,
+        }
+    }
+}
+
+impl From<aperture::VideoFormat> for VideoFormat {
+    fn from(value: aperture::VideoFormat) -> Self {
+        match value {
+            aperture::VideoFormat::Vp8Webm => Self::Vp8Webm,
+            aperture::VideoFormat::Vp9Webm => Self::Vp9Webm,
+            aperture::VideoFormat::Av1Webm => Self::Av1Webm,
+            aperture::VideoFormat::H264Mp4 => Self::H264Mp4,
+            aperture::VideoFormat::H264Matroska => Self::H264Matroska,
+        }
+    }
+}
+
+/// Enum representing the org.gnome.Snapshot.Quality enum defined in the
+/// gschema.
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+#[repr(u32)]
+pub enum Quality {
+    Small,
+    #[default]
+    Standard,
+    High,
+}
+
+impl Quality {
+    pub const ALL: [Self; 3] = [Self::Small, Self::Standard, Self::High];
+
+    pub fn translatable_string(&self) -> String {
+        match self {
+            // TRANSLATORS This is the capture quality presented in the
+            // preferences window.
+            Self::Small => gettext("Small"),
+            // TRANSLATORS This is the capture quality presented in the
+            // preferences window.
+            Self::Standard => gettext("Standard"),
+            // TRANSLATORS This is the capture quality presented in the
+            // preferences window.
+            Self::High => gettext("High"),
+        }
+    }
+
+    /// The picture quality, `0` uses the encoder default.
+    pub fn picture_quality(&self) -> u32 {
+        match self {
+            Self::Small => 70,
+            Self::Standard => 0,
+            Self::High => 95,
+        }
+    }
+
+    /// The video bitrate in kbit/s, `0` uses the encoder default.
+    pub fn video_bitrate(&self) -> u32 {
+        match self {
+            Self::Small => 1000,
+            Self::Standard => 0,
+            Self::High => 8000,
+        }
+    }
+
+    /// The audio bitrate in kbit/s, `0` uses the encoder default.
+    pub fn audio_bitrate(&self) -> u32 {
+        match self {
+            Self::Small => 64,
+            Self::Standard => 0,
+            Self::High => 192,
+        }
+    }
+}
+
+impl From<i32> for Quality {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Small,
+            1 => Self::Standard,
+            2 => Self::High,
             _ => Self::default(),
         }
     }
@@ -72,6 +261,8 @@ pub enum CaptureMode {
     #[default]
     Picture,
     Video,
+    Loop,
+    Timelapse,
 }
 
 impl From<i32> for CaptureMode {
@@ -79,11 +270,57 @@ impl From<i32> for CaptureMode {
         match value {
             0 => Self::Picture,
             1 => Self::Video,
+            2 => Self::Loop,
+            3 => Self::Timelapse,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// Enum representing the org.gnome.Snapshot.Effect enum defined in the
+/// gschema.
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+#[repr(u32)]
+pub enum Effect {
+    #[default]
+    None,
+    Mono,
+    Sepia,
+    HighContrast,
+    Vivid,
+    Warm,
+    Cool,
+}
+
+impl From<i32> for Effect {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Mono,
+            2 => Self::Sepia,
+            3 => Self::HighContrast,
+            4 => Self::Vivid,
+            5 => Self::Warm,
+            6 => Self::Cool,
             _ => Self::default(),
         }
     }
 }
 
+impl From<Effect> for aperture::VideoEffect {
+    fn from(value: Effect) -> Self {
+        match value {
+            Effect::None => Self::None,
+            Effect::Mono => Self::Mono,
+            Effect::Sepia => Self::Sepia,
+            Effect::HighContrast => Self::HighContrast,
+            Effect::Vivid => Self::Vivid,
+            Effect::Warm => Self::Warm,
+            Effect::Cool => Self::Cool,
+        }
+    }
+}
+
 #[derive(Default, Debug, Copy, Clone, glib::Enum, PartialEq)]
 #[repr(u32)]
 #[enum_type(name = "ShutterMode")]
@@ -92,6 +329,26 @@ pub enum ShutterMode {
     Picture,
     Video,
     Recording,
+    RecordingPaused,
+}
+
+/// The activities keeping the session from going idle, each inhibiting it on
+/// its own.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InhibitReason {
+    Recording,
+    Playback,
+    Intervalometer,
+}
+
+impl InhibitReason {
+    pub fn translatable_string(&self) -> String {
+        match self {
+            Self::Recording => gettext("Recording Video"),
+            Self::Playback => gettext("Playing Video"),
+            Self::Intervalometer => gettext("Taking Pictures"),
+        }
+    }
 }
 
 pub fn init() {
@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//! Geotagging through the `org.freedesktop.portal.Location` portal.
+//!
+//! The portal is looked up on the session bus, so it can be replaced by a
+//! mock service by running Snapshot in a separate session, e.g. with
+//! `dbus-run-session`, as done by the tests of this module.
+use std::pin::pin;
+
+use ashpd::desktop::location::{Accuracy, Location, LocationProxy};
+use ashpd::WindowIdentifier;
+use futures_channel::oneshot;
+use futures_util::StreamExt;
+
+/// Writes the location reported by the portal into the captures of
+/// `viewfinder`, until `cancel` is triggered or dropped.
+pub async fn track(
+    viewfinder: &aperture::Viewfinder,
+    cancel: oneshot::Receiver<()>,
+) -> ashpd::Result<()> {
+    let proxy = LocationProxy::new().await?;
+    let updates = proxy.receive_location_updated().await?;
+    let session = proxy
+        .create_session(None, None, Some(Accuracy::Exact))
+        .await?;
+    proxy
+        .start(&session, &WindowIdentifier::default())
+        .await?
+        .response()?;
+
+    let mut updates = pin!(updates.take_until(cancel));
+    while let Some(location) = updates.next().await {
+        log::debug!(
+            "org.freedesktop.portal.Location:LocationUpdated: {}, {}",
+            location.latitude(),
+            location.longitude()
+        );
+        viewfinder.set_geo_location(Some(geo_location(&location)));
+    }
+
+    session.close().await
+}
+
+fn geo_location(location: &Location) -> aperture::GeoLocation {
+    let mut geo_location = aperture::GeoLocation::new(location.latitude(), location.longitude())
+        .with_accuracy(location.accuracy());
+    if let Some(altitude) = location.altitude() {
+        geo_location = geo_location.with_altitude(altitude);
+    }
+
+    geo_location
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use std::time::Duration;
+
+    use futures_util::future::{self, Either};
+    use gtk::glib;
+    use zbus::names::BusName;
+    use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Structure, Value};
+
+    use super::*;
+
+    const LATITUDE: f64 = 48.8584;
+    const LONGITUDE: f64 = 2.2945;
+    const ALTITUDE: f64 = 35.0;
+    const ACCURACY: f64 = 10.0;
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// A private session bus, stopped when dropped.
+    struct SessionBus(Child);
+
+    impl SessionBus {
+        fn start() -> Self {
+            let mut child = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("Could not start dbus-daemon");
+
+            let mut address = String::new();
+            BufReader::new(child.stdout.take().unwrap())
+                .read_line(&mut address)
+                .unwrap();
+            std::env::set_var("DBUS_SESSION_BUS_ADDRESS", address.trim());
+
+            Self(child)
+        }
+    }
+
+    impl Drop for SessionBus {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    /// The path of a request or session object of the portal, see
+    /// `org.freedesktop.portal.Request`.
+    fn handle_path(kind: &str, sender: &str, token: &str) -> OwnedObjectPath {
+        let sender = sender.trim_start_matches(':').replace('.', "_");
+        OwnedObjectPath::try_from(format!(
+            "/org/freedesktop/portal/desktop/{kind}/{sender}/{token}"
+        ))
+        .unwrap()
+    }
+
+    fn token(options: &mut HashMap<String, OwnedValue>, key: &str) -> String {
+        options
+            .remove(key)
+            .and_then(|token| String::try_from(token).ok())
+            .unwrap_or_default()
+    }
+
+    /// A location portal granting every session and reporting a single
+    /// location once it is started.
+    struct MockLocation;
+
+    #[zbus::interface(name = "org.freedesktop.portal.Location")]
+    impl MockLocation {
+        fn create_session(
+            &self,
+            #[zbus(header)] header: zbus::message::Header<'_>,
+            mut options: HashMap<String, OwnedValue>,
+        ) -> OwnedObjectPath {
+            let token = token(&mut options, "session_handle_token");
+            handle_path("session", header.sender().unwrap(), &token)
+        }
+
+        async fn start(
+            &self,
+            #[zbus(header)] header: zbus::message::Header<'_>,
+            #[zbus(connection)] connection: &zbus::Connection,
+            session_handle: ObjectPath<'_>,
+            _parent_window: &str,
+            mut options: HashMap<String, OwnedValue>,
+        ) -> zbus::fdo::Result<OwnedObjectPath> {
+            let token = token(&mut options, "handle_token");
+            let request = handle_path("request", header.sender().unwrap(), &token);
+
+            connection
+                .emit_signal(
+                    None::<BusName<'_>>,
+                    &request,
+                    "org.freedesktop.portal.Request",
+                    "Response",
+                    &(0u32, HashMap::<&str, Value<'_>>::new()),
+                )
+                .await?;
+
+            let location = HashMap::from([
+                ("Latitude", Value::from(LATITUDE)),
+                ("Longitude", Value::from(LONGITUDE)),
+                ("Altitude", Value::from(ALTITUDE)),
+                ("Accuracy", Value::from(ACCURACY)),
+                ("Speed", Value::from(-1.0)),
+                ("Heading", Value::from(-1.0)),
+                ("Description", Value::from("")),
+                ("Timestamp", Value::from(Structure::from((0u64, 0u64)))),
+            ]);
+            connection
+                .emit_signal(
+                    None::<BusName<'_>>,
+                    "/org/freedesktop/portal/desktop",
+                    "org.freedesktop.portal.Location",
+                    "LocationUpdated",
+                    &(session_handle, location),
+                )
+                .await?;
+
+            Ok(request)
+        }
+
+        #[zbus(property, name = "version")]
+        fn version(&self) -> u32 {
+            1
+        }
+    }
+
+    #[gtk::test]
+    async fn test_track() {
+        let _bus = SessionBus::start();
+        let _portal = zbus::connection::Builder::session()
+            .unwrap()
+            .name("org.freedesktop.portal.Desktop")
+            .unwrap()
+            .serve_at("/org/freedesktop/portal/desktop", MockLocation)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        aperture::init(crate::config::APP_ID);
+        let viewfinder = aperture::Viewfinder::new();
+
+        let (notify_sender, notify_receiver) = oneshot::channel();
+        let notify_sender = Cell::new(Some(notify_sender));
+        viewfinder.connect_geo_location_notify(move |_| {
+            if let Some(sender) = notify_sender.take() {
+                let _ = sender.send(());
+            }
+        });
+
+        let (_cancel, cancel_receiver) = oneshot::channel::<()>();
+        let tracking = pin!(track(&viewfinder, cancel_receiver));
+        match glib::future_with_timeout(TIMEOUT, future::select(tracking, notify_receiver)).await {
+            Ok(Either::Left((result, _))) => panic!("Tracking stopped early: {result:?}"),
+            Ok(Either::Right(_)) => (),
+            Err(_) => panic!("No location was reported in {TIMEOUT:?}"),
+        }
+
+        let geo_location = viewfinder.geo_location().unwrap();
+        assert_eq!(geo_location.latitude(), LATITUDE);
+        assert_eq!(geo_location.longitude(), LONGITUDE);
+        assert_eq!(geo_location.altitude(), Some(ALTITUDE));
+        assert_eq!(geo_location.accuracy(), Some(ACCURACY));
+    }
+}
@@ -4,6 +4,8 @@ mod config;
 mod application;
 mod enums;
 mod i18n;
+#[cfg(feature = "portal")]
+mod location;
 mod utils;
 mod widgets;
 
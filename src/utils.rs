@@ -31,6 +31,22 @@ pub fn picture_file_name(picture_format: crate::PictureFormat) -> String {
     format!("{file_name}.{format}")
 }
 
+/// The name of the pictures of a burst, without their number and extension.
+pub fn burst_file_stem() -> String {
+    if let Ok(date_time) = glib::DateTime::now_local() {
+        let f_date = date_time.format(DATE_FORMAT).unwrap();
+        // TRANSLATORS Do NOT translate {date}. This will appear as, e.g.
+        // "Burst from 2023-05-21 11-05-59.12345 001" and it will be used as a
+        // file name.
+        i18n_f("Burst from {date}", &[("date", &f_date)])
+    } else {
+        let rand = glib::random_int_range(0, 999999).to_string();
+        // TRANSLATORS Do NOT translate {number}. This will appear as, e.g.
+        // "Burst 12345 001" and it will be used as a file name.
+        i18n_f("Burst {number}", &[("number", &rand)])
+    }
+}
+
 pub fn video_file_name(video_format: crate::VideoFormat) -> String {
     let format = video_format.as_str();
     let file_name = if let Ok(date_time) = glib::DateTime::now_local() {
@@ -75,6 +91,131 @@ pub fn pictures_dir() -> anyhow::Result<PathBuf> {
     Ok(path)
 }
 
+/// Serializes a camera mode for the `camera-modes` setting, e.g.
+/// `1920x1080@30/1`.
+pub fn mode_to_string(width: i32, height: i32, framerate: gst::Fraction) -> String {
+    format!(
+        "{width}x{height}@{}/{}",
+        framerate.numer(),
+        framerate.denom()
+    )
+}
+
+/// Parses a camera mode from the `camera-modes` setting.
+pub fn mode_from_string(mode: &str) -> Option<(i32, i32, gst::Fraction)> {
+    let (size, framerate) = mode.split_once('@')?;
+    let (width, height) = size.split_once('x')?;
+    let (numer, denom) = framerate.split_once('/')?;
+
+    let denom = denom.parse().ok()?;
+    if denom == 0 {
+        return None;
+    }
+
+    Some((
+        width.parse().ok()?,
+        height.parse().ok()?,
+        gst::Fraction::new(numer.parse().ok()?, denom),
+    ))
+}
+
+/// Finds the mode of `camera` stored in the `camera-modes` setting, if any.
+pub fn stored_camera_mode(
+    settings: &gio::Settings,
+    camera: &aperture::Camera,
+) -> Option<aperture::CameraMode> {
+    let modes = settings
+        .value("camera-modes")
+        .get::<std::collections::HashMap<String, String>>()?;
+    let (width, height, framerate) = mode_from_string(modes.get(camera_id(camera).as_str())?)?;
+
+    camera
+        .modes()
+        .into_iter()
+        .find(|mode| {
+            mode.width() == width
+                && mode.height() == height
+                && mode.framerates().contains(&framerate)
+        })
+        .map(|mode| mode.with_framerate(framerate))
+}
+
+/// Stores the mode of `camera` in the `camera-modes` setting, `None` removes
+/// it.
+pub fn store_camera_mode(
+    settings: &gio::Settings,
+    camera: &aperture::Camera,
+    mode: Option<(i32, i32, gst::Fraction)>,
+) {
+    let mut modes = settings
+        .value("camera-modes")
+        .get::<std::collections::HashMap<String, String>>()
+        .unwrap_or_default();
+    let id = camera_id(camera).to_string();
+    if let Some((width, height, framerate)) = mode {
+        modes.insert(id, mode_to_string(width, height, framerate));
+    } else {
+        modes.remove(&id);
+    }
+
+    if let Err(err) = settings.set_value("camera-modes", &modes.to_variant()) {
+        log::error!("Could not store the camera mode: {err}");
+    }
+}
+
+/// The brightness, contrast, saturation, hue and colour temperature of a
+/// camera, as stored in the `camera-adjustments` setting.
+pub type Adjustments = (f64, f64, f64, f64, f64);
+
+/// The adjustments leaving the camera feed untouched.
+pub const DEFAULT_ADJUSTMENTS: Adjustments = (0.0, 1.0, 1.0, 0.0, 0.0);
+
+/// Finds the adjustments of `camera` stored in the `camera-adjustments`
+/// setting, if any.
+pub fn stored_camera_adjustments(
+    settings: &gio::Settings,
+    camera: &aperture::Camera,
+) -> Option<Adjustments> {
+    let adjustments = settings
+        .value("camera-adjustments")
+        .get::<std::collections::HashMap<String, Adjustments>>()?;
+
+    adjustments.get(camera_id(camera).as_str()).copied()
+}
+
+/// Stores the adjustments of `camera` in the `camera-adjustments` setting,
+/// the default adjustments are removed.
+pub fn store_camera_adjustments(
+    settings: &gio::Settings,
+    camera: &aperture::Camera,
+    adjustments: Adjustments,
+) {
+    let mut stored = settings
+        .value("camera-adjustments")
+        .get::<std::collections::HashMap<String, Adjustments>>()
+        .unwrap_or_default();
+    let id = camera_id(camera).to_string();
+    if adjustments == DEFAULT_ADJUSTMENTS {
+        stored.remove(&id);
+    } else {
+        stored.insert(id, adjustments);
+    }
+
+    if let Err(err) = settings.set_value("camera-adjustments", &stored.to_variant()) {
+        log::error!("Could not store the camera adjustments: {err}");
+    }
+}
+
+// Id used to identify the cameras in the settings.
+pub fn camera_id(camera: &aperture::Camera) -> glib::GString {
+    camera.display_name()
+}
+
+// Id used to identify the microphones in the settings.
+pub fn microphone_id(microphone: &aperture::Microphone) -> glib::GString {
+    microphone.display_name()
+}
+
 static DEBUG_STR: Lazy<String> = Lazy::new(|| {
     let registry = gst::Registry::get();
     let mut version_string = String::new();
@@ -122,6 +263,10 @@ pub fn debug_info() -> String {
 
     let mut debug_string = format!("Library Details:\n\n{}", &*DEBUG_STR);
 
+    if let Some(backend) = device_provider.backend() {
+        debug_string.push_str(&format!("\n\nDevice Provider: {backend}"));
+    }
+
     if device_provider.camera(0).is_some() {
         debug_string.push_str("\n\nCameras:\n\n");
         debug_string.push_str(&camera_info);
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+mod imp {
+    use std::cell::RefCell;
+
+    use glib::Properties;
+    use gtk::CompositeTemplate;
+
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate, Properties)]
+    #[properties(wrapper_type = super::AdjustmentsPopover)]
+    #[template(resource = "/org/gnome/Snapshot/ui/adjustments_popover.ui")]
+    pub struct AdjustmentsPopover {
+        /// The viewfinder whose adjustments are shown.
+        #[property(get, set = Self::set_viewfinder, nullable, explicit_notify)]
+        pub viewfinder: RefCell<Option<aperture::Viewfinder>>,
+        pub bindings: RefCell<Vec<glib::Binding>>,
+
+        #[template_child]
+        pub brightness_adjustment: TemplateChild<gtk::Adjustment>,
+        #[template_child]
+        pub contrast_adjustment: TemplateChild<gtk::Adjustment>,
+        #[template_child]
+        pub saturation_adjustment: TemplateChild<gtk::Adjustment>,
+        #[template_child]
+        pub hue_adjustment: TemplateChild<gtk::Adjustment>,
+        #[template_child]
+        pub color_temperature_adjustment: TemplateChild<gtk::Adjustment>,
+    }
+
+    impl AdjustmentsPopover {
+        fn set_viewfinder(&self, viewfinder: Option<aperture::Viewfinder>) {
+            if viewfinder == *self.viewfinder.borrow() {
+                return;
+            }
+
+            for binding in self.bindings.take() {
+                binding.unbind();
+            }
+
+            if let Some(viewfinder) = &viewfinder {
+                let bindings = [
+                    ("brightness", &*self.brightness_adjustment),
+                    ("contrast", &*self.contrast_adjustment),
+                    ("saturation", &*self.saturation_adjustment),
+                    ("hue", &*self.hue_adjustment),
+                    ("color-temperature", &*self.color_temperature_adjustment),
+                ]
+                .into_iter()
+                .map(|(property, adjustment)| {
+                    viewfinder
+                        .bind_property(property, adjustment, "value")
+                        .bidirectional()
+                        .sync_create()
+                        .build()
+                })
+                .collect();
+                self.bindings.replace(bindings);
+            }
+
+            self.viewfinder.replace(viewfinder);
+            self.obj().notify_viewfinder();
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for AdjustmentsPopover {
+        const NAME: &'static str = "AdjustmentsPopover";
+        type Type = super::AdjustmentsPopover;
+        type ParentType = gtk::Popover;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[gtk::template_callbacks]
+    impl AdjustmentsPopover {
+        #[template_callback]
+        fn on_reset_button_clicked(&self) {
+            if let Some(viewfinder) = self.viewfinder.borrow().as_ref() {
+                viewfinder.set_brightness(0.0);
+                viewfinder.set_contrast(1.0);
+                viewfinder.set_saturation(1.0);
+                viewfinder.set_hue(0.0);
+                viewfinder.set_color_temperature(0.0);
+            }
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for AdjustmentsPopover {}
+    impl WidgetImpl for AdjustmentsPopover {}
+    impl PopoverImpl for AdjustmentsPopover {}
+}
+
+glib::wrapper! {
+    pub struct AdjustmentsPopover(ObjectSubclass<imp::AdjustmentsPopover>)
+        @extends gtk::Widget, gtk::Popover;
+}
+
+impl Default for AdjustmentsPopover {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
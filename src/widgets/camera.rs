@@ -13,6 +13,13 @@ use std::os::unix::io::OwnedFd;
 use super::CameraControls;
 use crate::{config, utils};
 
+/// The number of pictures taken by a burst.
+const BURST_COUNT: u32 = 10;
+/// The minimum interval between the pictures of a burst.
+const BURST_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+/// How long the camera adjustments have to stay unchanged before being stored.
+const ADJUSTMENTS_STORE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
 mod imp {
     use std::cell::{Cell, OnceCell, RefCell};
 
@@ -27,9 +34,19 @@ mod imp {
         settings: OnceCell<gio::Settings>,
         pub permission_denied: Cell<bool>,
 
+        // Set while the adjustments of a camera are restored, to not store
+        // them again.
+        pub is_restoring_adjustments: Cell<bool>,
+        // The pending store of the adjustments of a camera.
+        pub adjustments_store_source: RefCell<Option<(glib::SourceId, aperture::Camera)>>,
+
         pub recording_duration: Cell<u32>,
         pub recording_source: RefCell<Option<glib::source::SourceId>>,
 
+        // Dropping it stops tracking the location.
+        #[cfg(feature = "portal")]
+        pub location_cancel: RefCell<Option<futures_channel::oneshot::Sender<()>>>,
+
         #[template_child]
         pub single_landscape_bp: TemplateChild<adw::Breakpoint>,
         #[template_child]
@@ -41,6 +58,25 @@ mod imp {
         pub recording_revealer: TemplateChild<gtk::Revealer>,
         #[template_child]
         pub recording_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub audio_level_bar: TemplateChild<gtk::LevelBar>,
+        #[template_child]
+        pub pause_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub save_loop_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub location_revealer: TemplateChild<gtk::Revealer>,
+
+        #[template_child]
+        pub microphone_section: TemplateChild<gio::Menu>,
+        #[template_child]
+        pub horizontal_start_microphone_button: TemplateChild<gtk::MenuButton>,
+        #[template_child]
+        pub horizontal_end_microphone_button: TemplateChild<gtk::MenuButton>,
+        #[template_child]
+        pub vertical_start_microphone_button: TemplateChild<gtk::MenuButton>,
+        #[template_child]
+        pub vertical_end_microphone_button: TemplateChild<gtk::MenuButton>,
 
         #[template_child]
         pub viewfinder: TemplateChild<aperture::Viewfinder>,
@@ -123,6 +159,23 @@ mod imp {
             }));
             obj.update_cameras_button(provider);
 
+            provider.microphones().connect_items_changed(
+                glib::clone!(@weak obj => move |_, _, _, _| {
+                    obj.update_microphone_menu();
+                    obj.update_microphone();
+                }),
+            );
+            obj.update_microphone_menu();
+            for key in ["microphone", "record-audio"] {
+                self.settings().connect_changed(
+                    Some(key),
+                    glib::clone!(@weak obj => move |_, _| {
+                        obj.update_microphone();
+                    }),
+                );
+            }
+            obj.update_microphone();
+
             self.viewfinder
                 .connect_state_notify(glib::clone!(@weak obj => move |_| {
                     obj.update_state();
@@ -135,17 +188,100 @@ mod imp {
 
                     if viewfinder.is_recording() {
                         obj.set_shutter_mode(crate::ShutterMode::Recording);
-                        window.inhibit("Recording Video");
+                        window.inhibit(crate::InhibitReason::Recording);
+                        obj.update_pause_button();
+                        obj.imp()
+                            .save_loop_button
+                            .set_visible(viewfinder.is_loop_recording());
                         obj.show_recording_label();
                     } else {
                         obj.hide_recording_label();
-                        window.uninhibit();
-                        if matches!(obj.shutter_mode(), crate::ShutterMode::Recording) {
+                        window.uninhibit(crate::InhibitReason::Recording);
+                        // Changes done while recording are applied now.
+                        obj.update_microphone();
+                        if matches!(
+                            obj.shutter_mode(),
+                            crate::ShutterMode::Recording | crate::ShutterMode::RecordingPaused
+                        ) {
                             obj.set_shutter_mode(crate::ShutterMode::Video);
                         }
                     }
                 }),
             );
+            self.viewfinder.connect_is_recording_paused_notify(
+                glib::clone!(@weak obj => move |viewfinder| {
+                    if !viewfinder.is_recording() {
+                        return;
+                    }
+
+                    if viewfinder.is_recording_paused() {
+                        obj.set_shutter_mode(crate::ShutterMode::RecordingPaused);
+                    } else {
+                        obj.set_shutter_mode(crate::ShutterMode::Recording);
+                    }
+                    obj.update_pause_button();
+                }),
+            );
+
+            self.viewfinder
+                .connect_camera_notify(glib::clone!(@weak obj => move |_| {
+                    // The adjustments still belong to the previous camera.
+                    obj.flush_camera_adjustments();
+                    obj.update_camera_mode();
+                    obj.restore_camera_adjustments();
+                }));
+            for property in [
+                "brightness",
+                "contrast",
+                "saturation",
+                "hue",
+                "color-temperature",
+            ] {
+                self.viewfinder.connect_notify_local(
+                    Some(property),
+                    glib::clone!(@weak obj => move |_, _| {
+                        obj.queue_store_camera_adjustments();
+                    }),
+                );
+            }
+            self.viewfinder
+                .connect_audio_level(glib::clone!(@weak obj => move |_, level| {
+                    obj.update_audio_level(level);
+                }));
+            self.settings().connect_changed(
+                Some("picture-format"),
+                glib::clone!(@weak obj => move |_, _| {
+                    obj.update_picture_format();
+                }),
+            );
+            obj.update_picture_format();
+            self.settings().connect_changed(
+                Some("video-format"),
+                glib::clone!(@weak obj => move |_, _| {
+                    obj.update_video_format();
+                }),
+            );
+            obj.update_video_format();
+            self.settings().connect_changed(
+                Some("quality"),
+                glib::clone!(@weak obj => move |_, _| {
+                    obj.update_quality();
+                }),
+            );
+            obj.update_quality();
+            self.settings().connect_changed(
+                Some("effect"),
+                glib::clone!(@weak obj => move |_, _| {
+                    obj.update_effect();
+                }),
+            );
+            obj.update_effect();
+            self.settings().connect_changed(
+                Some("camera-modes"),
+                glib::clone!(@weak obj => move |_, _| {
+                    obj.update_camera_mode();
+                }),
+            );
 
             self.selection.set_model(Some(provider));
             self.selection.connect_selected_item_notify(
@@ -183,6 +319,74 @@ mod imp {
                     "draw-guidelines",
                 )
                 .build();
+            self.settings()
+                .bind("strip-metadata", &*self.viewfinder, "strip-metadata")
+                .get()
+                .build();
+            self.settings()
+                .bind(
+                    "max-recording-duration",
+                    &*self.viewfinder,
+                    "max-recording-duration",
+                )
+                .get()
+                .build();
+            self.settings()
+                .bind(
+                    "max-recording-size",
+                    &*self.viewfinder,
+                    "max-recording-size",
+                )
+                .get()
+                .build();
+            self.settings()
+                .bind("loop-duration", &*self.viewfinder, "loop-duration")
+                .get()
+                .build();
+            self.settings()
+                .bind(
+                    "loop-segment-duration",
+                    &*self.viewfinder,
+                    "loop-segment-duration",
+                )
+                .get()
+                .build();
+            self.settings().connect_changed(
+                Some("capture-mode"),
+                glib::clone!(@weak obj => move |_, _| {
+                    obj.update_timelapse();
+                }),
+            );
+            self.settings().connect_changed(
+                Some("timelapse-interval"),
+                glib::clone!(@weak obj => move |_, _| {
+                    obj.update_timelapse();
+                }),
+            );
+            obj.update_timelapse();
+            self.settings().connect_changed(
+                Some("split-recordings"),
+                glib::clone!(@weak obj => move |_, _| {
+                    obj.update_recording_limit_action();
+                }),
+            );
+            obj.update_recording_limit_action();
+
+            self.settings().connect_changed(
+                Some("geotag"),
+                glib::clone!(@weak obj => move |_, _| {
+                    obj.update_geotag();
+                }),
+            );
+            obj.update_geotag();
+            self.viewfinder
+                .connect_geo_location_notify(glib::clone!(@weak obj => move |_| {
+                    obj.update_location_indicator();
+                }));
+            self.viewfinder
+                .connect_strip_metadata_notify(glib::clone!(@weak obj => move |_| {
+                    obj.update_location_indicator();
+                }));
 
             // TODO remove if
             // https://gitlab.gnome.org/GNOME/gtk/-/merge_requests/5960 ever
@@ -206,6 +410,10 @@ mod imp {
                 }
             }));
         }
+
+        fn dispose(&self) {
+            self.obj().flush_camera_adjustments();
+        }
     }
 
     impl WidgetImpl for Camera {}
@@ -254,7 +462,7 @@ impl Camera {
 
                 if let Err(err) = provider.start_with_default(glib::clone!(@weak obj => @default-return false, move |camera| {
                     let stored_id = obj.imp().settings().string("last-camera-id");
-                    !stored_id.is_empty() && id_from_pw(camera) == stored_id
+                    !stored_id.is_empty() && utils::camera_id(camera) == stored_id
                 })) {
                     log::error!("Could not start the device provider: {err}");
                 } else {
@@ -265,7 +473,9 @@ impl Camera {
         );
     }
 
-    pub async fn start_recording(&self, format: crate::VideoFormat) -> anyhow::Result<()> {
+    pub async fn start_recording(&self) -> anyhow::Result<()> {
+        // The format in the settings might not be supported.
+        let format = crate::VideoFormat::from(self.imp().viewfinder.video_format());
         let filename = utils::video_file_name(format);
         let path = utils::videos_dir()?.join(filename);
 
@@ -274,6 +484,20 @@ impl Camera {
         Ok(())
     }
 
+    pub async fn start_loop_recording(&self) -> anyhow::Result<()> {
+        let format = crate::VideoFormat::from(self.imp().viewfinder.video_format());
+        let filename = utils::video_file_name(format);
+        let path = utils::videos_dir()?.join(filename);
+
+        self.imp().viewfinder.start_loop_recording(path)?;
+
+        Ok(())
+    }
+
+    pub fn save_loop_recording(&self) -> Result<(), aperture::CaptureError> {
+        self.imp().viewfinder.save_loop_recording()
+    }
+
     pub fn stop_recording(&self) {
         let imp = self.imp();
         if matches!(imp.viewfinder.state(), aperture::ViewfinderState::Ready)
@@ -285,13 +509,52 @@ impl Camera {
         }
     }
 
-    pub async fn take_picture(&self, format: crate::PictureFormat) -> anyhow::Result<()> {
+    pub fn toggle_pause_recording(&self) {
+        let viewfinder = &self.imp().viewfinder;
+        if !viewfinder.is_recording() {
+            return;
+        }
+
+        let res = if viewfinder.is_recording_paused() {
+            viewfinder.resume_recording()
+        } else {
+            viewfinder.pause_recording()
+        };
+        if let Err(err) = res {
+            log::error!("Could not pause or resume the recording: {err}");
+        }
+    }
+
+    fn update_pause_button(&self) {
+        let imp = self.imp();
+        if imp.viewfinder.is_recording_paused() {
+            imp.pause_button.set_icon_name("media-record-symbolic");
+            imp.pause_button
+                .set_tooltip_text(Some(&gettext("Resume Recording")));
+        } else {
+            imp.pause_button
+                .set_icon_name("media-playback-pause-symbolic");
+            imp.pause_button
+                .set_tooltip_text(Some(&gettext("Pause Recording")));
+        }
+    }
+
+    pub async fn take_picture(&self) -> anyhow::Result<()> {
         let imp = self.imp();
         let window = self.root().and_downcast::<crate::Window>().unwrap();
 
-        // We enable the shutter whenever picture-stored is emited.
-        window.set_shutter_enabled(false);
+        // Pictures taken while recording do not block the shutter, which stops
+        // the recording, and are always saved as JPEG.
+        let is_recording = imp.viewfinder.is_recording();
+        let format = if is_recording {
+            crate::PictureFormat::Jpeg
+        } else {
+            // We enable the shutter whenever picture-stored is emited.
+            window.set_shutter_enabled(false);
 
+            // The format in the settings might not be supported.
+            crate::PictureFormat::from(imp.viewfinder.picture_format())
+        };
         let filename = utils::picture_file_name(format);
         let path = utils::pictures_dir()?.join(filename);
 
@@ -306,6 +569,21 @@ impl Camera {
         Ok(())
     }
 
+    pub fn take_burst(&self) -> anyhow::Result<()> {
+        let imp = self.imp();
+
+        let stem = utils::pictures_dir()?.join(utils::burst_file_stem());
+        imp.viewfinder
+            .take_burst(BURST_COUNT, BURST_INTERVAL, stem)?;
+        imp.flash_bin.flash();
+
+        if imp.settings().boolean("play-shutter-sound") {
+            self.play_shutter_sound();
+        }
+
+        Ok(())
+    }
+
     fn camera_switched(&self) {
         let provider = self.imp().provider.get().unwrap();
 
@@ -324,13 +602,275 @@ impl Camera {
         let imp = self.imp();
 
         if let Some(ref camera) = camera {
-            let id = id_from_pw(camera);
+            let id = utils::camera_id(camera);
             imp.settings().set_string("last-camera-id", &id).unwrap();
         }
 
         imp.viewfinder.set_camera(camera);
     }
 
+    pub fn camera(&self) -> Option<aperture::Camera> {
+        self.imp().viewfinder.camera()
+    }
+
+    fn update_camera_mode(&self) {
+        let imp = self.imp();
+        let Some(camera) = imp.viewfinder.camera() else {
+            return;
+        };
+
+        let mode = utils::stored_camera_mode(imp.settings(), &camera);
+        if mode != imp.viewfinder.mode() {
+            imp.viewfinder.set_mode(mode);
+        }
+    }
+
+    fn restore_camera_adjustments(&self) {
+        let imp = self.imp();
+        let Some(camera) = imp.viewfinder.camera() else {
+            return;
+        };
+
+        let (brightness, contrast, saturation, hue, color_temperature) =
+            utils::stored_camera_adjustments(imp.settings(), &camera)
+                .unwrap_or(utils::DEFAULT_ADJUSTMENTS);
+
+        imp.is_restoring_adjustments.set(true);
+        imp.viewfinder.set_brightness(brightness);
+        imp.viewfinder.set_contrast(contrast);
+        imp.viewfinder.set_saturation(saturation);
+        imp.viewfinder.set_hue(hue);
+        imp.viewfinder.set_color_temperature(color_temperature);
+        imp.is_restoring_adjustments.set(false);
+    }
+
+    /// Stores the adjustments once they stop changing, as sliders notify
+    /// them continuously.
+    fn queue_store_camera_adjustments(&self) {
+        let imp = self.imp();
+        if imp.is_restoring_adjustments.get() {
+            return;
+        }
+        let Some(camera) = imp.viewfinder.camera() else {
+            return;
+        };
+
+        let source = glib::timeout_add_local_once(
+            ADJUSTMENTS_STORE_DELAY,
+            glib::clone!(@weak self as obj => move || {
+                if let Some((_, camera)) = obj.imp().adjustments_store_source.take() {
+                    obj.store_camera_adjustments(&camera);
+                }
+            }),
+        );
+        if let Some((old_source, _)) = imp.adjustments_store_source.replace(Some((source, camera)))
+        {
+            old_source.remove();
+        }
+    }
+
+    /// Stores the pending adjustments right away.
+    fn flush_camera_adjustments(&self) {
+        if let Some((source, camera)) = self.imp().adjustments_store_source.take() {
+            source.remove();
+            self.store_camera_adjustments(&camera);
+        }
+    }
+
+    fn store_camera_adjustments(&self, camera: &aperture::Camera) {
+        let imp = self.imp();
+        let viewfinder = &imp.viewfinder;
+        let adjustments = (
+            viewfinder.brightness(),
+            viewfinder.contrast(),
+            viewfinder.saturation(),
+            viewfinder.hue(),
+            viewfinder.color_temperature(),
+        );
+        utils::store_camera_adjustments(imp.settings(), camera, adjustments);
+    }
+
+    fn update_picture_format(&self) {
+        let imp = self.imp();
+
+        let format = crate::PictureFormat::from(imp.settings().enum_("picture-format"));
+        let format = aperture::PictureFormat::from(format);
+        if format.is_supported() {
+            imp.viewfinder.set_picture_format(format);
+        } else {
+            log::warn!("Picture format {format:?} is not supported, using JPEG");
+            imp.viewfinder
+                .set_picture_format(aperture::PictureFormat::Jpeg);
+        }
+    }
+
+    fn update_video_format(&self) {
+        let imp = self.imp();
+
+        let format = crate::VideoFormat::from(imp.settings().enum_("video-format"));
+        let format = aperture::VideoFormat::from(format);
+        if format.is_supported() {
+            imp.viewfinder.set_video_format(format);
+        } else {
+            log::warn!("Video format {format:?} is not supported, using VP8");
+            imp.viewfinder
+                .set_video_format(aperture::VideoFormat::Vp8Webm);
+        }
+    }
+
+    fn update_quality(&self) {
+        let imp = self.imp();
+
+        let quality = crate::Quality::from(imp.settings().enum_("quality"));
+        imp.viewfinder.reconfigure(|viewfinder| {
+            viewfinder.set_picture_quality(quality.picture_quality());
+            viewfinder.set_video_bitrate(quality.video_bitrate());
+            viewfinder.set_audio_bitrate(quality.audio_bitrate());
+        });
+    }
+
+    fn update_effect(&self) {
+        let imp = self.imp();
+
+        let effect = crate::Effect::from(imp.settings().enum_("effect"));
+        imp.viewfinder
+            .set_effect(aperture::VideoEffect::from(effect));
+    }
+
+    fn update_timelapse(&self) {
+        let imp = self.imp();
+        let settings = imp.settings();
+
+        let capture_mode = crate::CaptureMode::from(settings.enum_("capture-mode"));
+        let interval = if matches!(capture_mode, crate::CaptureMode::Timelapse) {
+            settings.uint("timelapse-interval").max(1) * 1000
+        } else {
+            0
+        };
+        imp.viewfinder.set_timelapse_interval(interval);
+    }
+
+    fn update_recording_limit_action(&self) {
+        let imp = self.imp();
+
+        let action = if imp.settings().boolean("split-recordings") {
+            aperture::RecordingLimitAction::Split
+        } else {
+            aperture::RecordingLimitAction::Stop
+        };
+        imp.viewfinder.set_recording_limit_action(action);
+    }
+
+    fn update_microphone_menu(&self) {
+        let imp = self.imp();
+        let provider = imp.provider.get().unwrap();
+
+        let section = &imp.microphone_section;
+        section.remove_all();
+
+        let default_item = gio::MenuItem::new(Some(&gettext("Default")), None);
+        default_item.set_action_and_target_value(Some("win.microphone"), Some(&"".to_variant()));
+        section.append_item(&default_item);
+
+        for microphone in provider
+            .microphones()
+            .iter::<aperture::Microphone>()
+            .filter_map(Result::ok)
+        {
+            let id = utils::microphone_id(&microphone);
+            let label = microphone
+                .nick()
+                .unwrap_or_else(|| microphone.display_name().to_string());
+            let item = gio::MenuItem::new(Some(&label), None);
+            item.set_action_and_target_value(Some("win.microphone"), Some(&id.to_variant()));
+            section.append_item(&item);
+        }
+    }
+
+    fn update_microphone(&self) {
+        let imp = self.imp();
+        let settings = imp.settings();
+
+        let record_audio = settings.boolean("record-audio");
+        let icon_name = if record_audio {
+            "audio-input-microphone-symbolic"
+        } else {
+            "microphone-disabled-symbolic"
+        };
+        imp.audio_level_bar.set_visible(record_audio);
+        for button in [
+            &imp.horizontal_start_microphone_button,
+            &imp.horizontal_end_microphone_button,
+            &imp.vertical_start_microphone_button,
+            &imp.vertical_end_microphone_button,
+        ] {
+            button.set_icon_name(icon_name);
+        }
+
+        // The viewfinder cannot change its audio source while recording.
+        if imp.viewfinder.is_recording() {
+            return;
+        }
+
+        let id = settings.string("microphone");
+        let microphone = imp
+            .provider
+            .get()
+            .unwrap()
+            .microphones()
+            .iter::<aperture::Microphone>()
+            .filter_map(Result::ok)
+            .find(|microphone| !id.is_empty() && utils::microphone_id(microphone) == id);
+        if microphone != imp.viewfinder.microphone() {
+            imp.viewfinder.set_microphone(microphone);
+        }
+        imp.viewfinder.set_record_audio(record_audio);
+    }
+
+    fn update_audio_level(&self, level: &aperture::AudioLevel) {
+        // The peak level is in dB, the level bar goes from silence to full
+        // scale.
+        let value = level
+            .max_peak()
+            .map(|peak| 10f64.powf(peak / 20.0).clamp(0.0, 1.0))
+            .unwrap_or_default();
+        self.imp().audio_level_bar.set_value(value);
+    }
+
+    fn update_geotag(&self) {
+        let imp = self.imp();
+
+        #[cfg(feature = "portal")]
+        imp.location_cancel.take();
+        imp.viewfinder
+            .set_geo_location(None::<aperture::GeoLocation>);
+
+        if imp.settings().boolean("geotag") {
+            #[cfg(feature = "portal")]
+            {
+                let (sender, receiver) = futures_channel::oneshot::channel();
+                imp.location_cancel.replace(Some(sender));
+
+                let viewfinder = imp.viewfinder.get();
+                glib::spawn_future_local(async move {
+                    if let Err(err) = crate::location::track(&viewfinder, receiver).await {
+                        log::warn!("Could not use the location portal: {err}");
+                    }
+                });
+            }
+            #[cfg(not(feature = "portal"))]
+            log::warn!("Geotagging is not available, Snapshot was built without portal support");
+        }
+    }
+
+    fn update_location_indicator(&self) {
+        let imp = self.imp();
+
+        let is_geotagging =
+            imp.viewfinder.geo_location().is_some() && !imp.viewfinder.strip_metadata();
+        imp.location_revealer.set_reveal_child(is_geotagging);
+    }
+
     fn play_shutter_sound(&self) {
         // If we don't hold a reference to it there is a condition race which
         // will cause the sound to play only sometimes.
@@ -372,6 +912,14 @@ impl Camera {
         self.imp().camera_controls_vertical.stop_countdown();
     }
 
+    pub fn set_remaining_shots(&self, remaining_shots: u32) {
+        let imp = self.imp();
+        imp.camera_controls_horizontal
+            .set_remaining_shots(remaining_shots);
+        imp.camera_controls_vertical
+            .set_remaining_shots(remaining_shots);
+    }
+
     pub fn shutter_mode(&self) -> crate::ShutterMode {
         self.active_controls().shutter_mode()
     }
@@ -392,7 +940,7 @@ impl Camera {
         let imp = self.imp();
 
         imp.viewfinder.connect_picture_done(
-            glib::clone!(@weak gallery, @weak self as obj => move |_, file| {
+            glib::clone!(@weak gallery, @weak self as obj => move |_, file, _| {
                 let window = obj.root().and_downcast::<crate::Window>().unwrap();
                 window.set_shutter_enabled(true);
                 // TODO Maybe report error via toast on None
@@ -410,6 +958,13 @@ impl Camera {
                 }
             }),
         );
+        imp.viewfinder
+            .connect_burst_progress(glib::clone!(@weak gallery => move |_, _, file| {
+                gallery.add_image(file);
+            }));
+        imp.viewfinder.connect_burst_done(|_, files| {
+            log::debug!("Burst done, {} pictures saved", files.n_items());
+        });
         imp.camera_controls_horizontal.set_gallery(&gallery);
         imp.camera_controls_vertical.set_gallery(&gallery);
     }
@@ -512,14 +1067,15 @@ impl Camera {
             1,
             glib::clone!(@weak self as obj => @default-return glib::ControlFlow::Break, move || {
                 let imp = obj.imp();
+                if imp.viewfinder.is_recording_paused() {
+                    return glib::ControlFlow::Continue;
+                }
 
                 // TODO Use Cell::update once stabilized.
                 let duration = imp.recording_duration.get() + 1;
                 imp.recording_duration.set(duration);
 
-                let minutes = duration.div_euclid(60);
-                let seconds = duration.rem_euclid(60);
-                imp.recording_label.set_label(&format!("{minutes}∶{seconds:02}"));
+                imp.recording_label.set_label(&obj.recording_label(duration));
 
                 glib::ControlFlow::Continue
             }),
@@ -529,7 +1085,28 @@ impl Camera {
         }
         imp.recording_duration.set(0);
         imp.recording_revealer.set_reveal_child(true);
-        imp.recording_label.set_label("0∶00");
+        imp.recording_label.set_label(&self.recording_label(0));
+        imp.audio_level_bar.set_value(0.0);
+    }
+
+    // The elapsed time, and the length of the resulting clip for time-lapses.
+    fn recording_label(&self, duration: u32) -> String {
+        let viewfinder = &self.imp().viewfinder;
+
+        let interval = viewfinder.timelapse_interval();
+        if interval == 0 {
+            return format_duration(duration);
+        }
+
+        // The first frame is recorded right away.
+        let frames = u64::from(duration) * 1000 / u64::from(interval) + 1;
+        let clip_duration = frames / u64::from(viewfinder.timelapse_framerate().max(1));
+
+        format!(
+            "{} → {}",
+            format_duration(duration),
+            format_duration(clip_duration as u32)
+        )
     }
 
     fn hide_recording_label(&self) {
@@ -554,7 +1131,8 @@ async fn stream() -> ashpd::Result<OwnedFd> {
     proxy.open_pipe_wire_remote().await
 }
 
-// Id used to identify the last-used camera.
-fn id_from_pw(camera: &aperture::Camera) -> glib::GString {
-    camera.display_name()
+fn format_duration(duration: u32) -> String {
+    let minutes = duration.div_euclid(60);
+    let seconds = duration.rem_euclid(60);
+    format!("{minutes}∶{seconds:02}")
 }
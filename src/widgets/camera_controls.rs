@@ -165,6 +165,12 @@ impl CameraControls {
         self.imp().shutter_button.stop_countdown();
     }
 
+    pub fn set_remaining_shots(&self, remaining_shots: u32) {
+        self.imp()
+            .shutter_button
+            .set_remaining_shots(remaining_shots);
+    }
+
     pub fn shutter_mode(&self) -> crate::ShutterMode {
         self.imp().shutter_button.shutter_mode()
     }
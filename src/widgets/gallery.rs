@@ -9,6 +9,7 @@ use once_cell::sync::Lazy;
 static ATTRIBUTES: Lazy<String> = Lazy::new(|| {
     [
         gio::FILE_ATTRIBUTE_STANDARD_NAME.as_str(),
+        gio::FILE_ATTRIBUTE_STANDARD_CONTENT_TYPE.as_str(),
         gio::FILE_ATTRIBUTE_TIME_CREATED.as_str(),
         gio::FILE_ATTRIBUTE_TIME_CREATED_USEC.as_str(),
         gio::FILE_ATTRIBUTE_TIME_MODIFIED.as_str(),
@@ -297,7 +298,22 @@ impl Gallery {
             let name = file_info.name();
             let file = gio::File::for_path(&dir.join(&name));
 
-            // TODO Do not add items with wrong mime type.
+            let is_supported = file_info.content_type().is_some_and(|content_type| {
+                let mime_type = gio::content_type_get_mime_type(&content_type);
+                if is_picture {
+                    mime_type.is_some_and(|mime_type| {
+                        crate::PictureFormat::ALL
+                            .iter()
+                            .any(|format| format.mime_type() == mime_type)
+                    })
+                } else {
+                    mime_type.is_some_and(|mime_type| mime_type.starts_with("video/"))
+                }
+            });
+            if !is_supported {
+                log::debug!("Skipping file with unsupported type: {name:?}");
+                continue;
+            }
 
             // NOTE Filesystems that do not support either creation or modified
             // dates will get files with a random ordering.
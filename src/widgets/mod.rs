@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 use gtk::prelude::*;
 
+mod adjustments_popover;
 mod camera;
 mod camera_controls;
 mod camera_row;
@@ -17,6 +18,7 @@ mod sliding_view;
 mod video_player;
 mod window;
 
+pub use adjustments_popover::AdjustmentsPopover;
 pub use camera::Camera;
 pub use camera_controls::CameraControls;
 pub use camera_row::CameraRow;
@@ -34,6 +36,7 @@ pub use video_player::VideoPlayer;
 pub use window::Window;
 
 pub fn init() {
+    AdjustmentsPopover::static_type();
     Camera::static_type();
     Gallery::static_type();
     GalleryButton::static_type();
@@ -1,20 +1,55 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 use adw::prelude::*;
 use adw::subclass::prelude::*;
+use gettextrs::{gettext, ngettext};
 use gtk::CompositeTemplate;
 use gtk::{gio, glib};
 
-use crate::config;
+use crate::i18n::{freplace, i18n_f};
+use crate::{config, utils};
+
+/// The intervals offered for time-lapses, in seconds.
+const TIMELAPSE_INTERVALS: [u32; 6] = [1, 2, 5, 10, 30, 60];
+/// The numbers of pictures offered for the intervalometer, 0 stands for
+/// taking pictures until stopped.
+const INTERVALOMETER_SHOTS: [u32; 6] = [0, 5, 10, 20, 50, 100];
 
 mod imp {
-    use std::cell::OnceCell;
+    use std::cell::{Cell, OnceCell, RefCell};
 
     use super::*;
 
     #[derive(Debug, Default, CompositeTemplate)]
     #[template(resource = "/org/gnome/Snapshot/ui/preferences_window.ui")]
     pub struct PreferencesWindow {
-        settings: OnceCell<gio::Settings>,
+        pub settings: OnceCell<gio::Settings>,
+        pub camera: RefCell<Option<aperture::Camera>>,
+        // The resolutions offered by the resolution row, `None` stands for
+        // automatic.
+        pub resolutions: RefCell<Vec<Option<(i32, i32)>>>,
+        pub framerates: RefCell<Vec<gst::Fraction>>,
+        // Set while the rows are being populated, so that we don't store
+        // intermediate selections.
+        pub updating: Cell<bool>,
+
+        #[template_child]
+        pub geotag_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub picture_format_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub video_format_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub quality_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub intervalometer_shots_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub timelapse_interval_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub camera_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub resolution_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub framerate_row: TemplateChild<adw::ComboRow>,
     }
 
     #[glib::object_subclass]
@@ -36,6 +71,8 @@ mod imp {
         fn constructed(&self) {
             self.parent_constructed();
 
+            let obj = self.obj();
+
             let settings = gio::Settings::new(config::APP_ID);
 
             let action_group = gio::SimpleActionGroup::new();
@@ -44,11 +81,48 @@ mod imp {
             action_group.add_action(&play_shutter_sound);
             let show_composition_guidelines = settings.create_action("show-composition-guidelines");
             action_group.add_action(&show_composition_guidelines);
+            let strip_metadata = settings.create_action("strip-metadata");
+            action_group.add_action(&strip_metadata);
+            let geotag = settings.create_action("geotag");
+            action_group.add_action(&geotag);
 
-            self.obj()
-                .insert_action_group("preferences-window", Some(&action_group));
+            obj.insert_action_group("preferences-window", Some(&action_group));
+
+            // The location can only be obtained through the portal.
+            self.geotag_row.set_visible(cfg!(feature = "portal"));
 
             self.settings.set(settings).unwrap();
+
+            obj.setup_format_row(
+                &self.picture_format_row,
+                "picture-format",
+                crate::PictureFormat::ALL
+                    .into_iter()
+                    .filter(|format| aperture::PictureFormat::from(*format).is_supported())
+                    .map(|format| (format as i32, format.translatable_string()))
+                    .collect(),
+            );
+            obj.setup_format_row(
+                &self.video_format_row,
+                "video-format",
+                crate::VideoFormat::ALL
+                    .into_iter()
+                    .filter(|format| aperture::VideoFormat::from(*format).is_supported())
+                    .map(|format| (format as i32, format.translatable_string()))
+                    .collect(),
+            );
+            obj.setup_quality();
+            obj.setup_intervalometer_shots();
+            obj.setup_timelapse_interval();
+
+            self.resolution_row
+                .connect_selected_notify(glib::clone!(@weak obj => move |_| {
+                    obj.on_resolution_selected();
+                }));
+            self.framerate_row
+                .connect_selected_notify(glib::clone!(@weak obj => move |_| {
+                    obj.store_camera_mode();
+                }));
         }
     }
 
@@ -67,3 +141,286 @@ impl Default for PreferencesWindow {
         glib::Object::new()
     }
 }
+
+impl PreferencesWindow {
+    /// Creates the preferences window, offering the modes of `camera`.
+    pub fn new(camera: Option<&aperture::Camera>) -> Self {
+        let obj = Self::default();
+        if let Some(camera) = camera {
+            obj.set_camera(camera);
+        }
+
+        obj
+    }
+
+    /// Offers `formats`, the values and labels of the supported formats, in
+    /// `row`, selecting and storing the format of the `key` setting.
+    fn setup_format_row(
+        &self,
+        row: &adw::ComboRow,
+        key: &'static str,
+        formats: Vec<(i32, String)>,
+    ) {
+        let settings = self.imp().settings.get().unwrap();
+
+        let current = settings.enum_(key);
+        let selected = formats
+            .iter()
+            .position(|(format, _)| *format == current)
+            .unwrap_or_default();
+
+        let model = gtk::StringList::new(
+            &formats
+                .iter()
+                .map(|(_, label)| label.as_str())
+                .collect::<Vec<_>>(),
+        );
+        row.set_model(Some(&model));
+        row.set_selected(selected as u32);
+
+        row.connect_selected_notify(glib::clone!(@weak settings => move |row| {
+            if let Some((format, _)) = formats.get(row.selected() as usize) {
+                if let Err(err) = settings.set_enum(key, *format) {
+                    log::error!("Could not store the {key} setting: {err}");
+                }
+            }
+        }));
+    }
+
+    fn setup_quality(&self) {
+        let imp = self.imp();
+        let settings = imp.settings.get().unwrap();
+
+        let labels = crate::Quality::ALL
+            .iter()
+            .map(|quality| quality.translatable_string())
+            .collect::<Vec<_>>();
+        let current = crate::Quality::from(settings.enum_("quality"));
+        let selected = crate::Quality::ALL
+            .iter()
+            .position(|quality| *quality == current)
+            .unwrap_or_default();
+
+        let model = gtk::StringList::new(&labels.iter().map(String::as_str).collect::<Vec<_>>());
+        imp.quality_row.set_model(Some(&model));
+        imp.quality_row.set_selected(selected as u32);
+
+        imp.quality_row
+            .connect_selected_notify(glib::clone!(@weak settings => move |row| {
+                if let Some(quality) = crate::Quality::ALL.get(row.selected() as usize) {
+                    if let Err(err) = settings.set_enum("quality", *quality as i32) {
+                        log::error!("Could not store the quality: {err}");
+                    }
+                }
+            }));
+    }
+
+    fn setup_intervalometer_shots(&self) {
+        let imp = self.imp();
+        let settings = imp.settings.get().unwrap();
+
+        let labels = INTERVALOMETER_SHOTS
+            .iter()
+            .map(|shots| match shots {
+                0 => gettext("Until Stopped"),
+                shots => shots.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let current = settings.uint("intervalometer-shots");
+        let selected = INTERVALOMETER_SHOTS
+            .iter()
+            .position(|shots| *shots == current)
+            .unwrap_or(2);
+
+        let model = gtk::StringList::new(&labels.iter().map(String::as_str).collect::<Vec<_>>());
+        imp.intervalometer_shots_row.set_model(Some(&model));
+        imp.intervalometer_shots_row.set_selected(selected as u32);
+
+        imp.intervalometer_shots_row.connect_selected_notify(
+            glib::clone!(@weak settings => move |row| {
+                if let Some(shots) = INTERVALOMETER_SHOTS.get(row.selected() as usize) {
+                    if let Err(err) = settings.set_uint("intervalometer-shots", *shots) {
+                        log::error!("Could not store the intervalometer shots: {err}");
+                    }
+                }
+            }),
+        );
+    }
+
+    fn setup_timelapse_interval(&self) {
+        let imp = self.imp();
+        let settings = imp.settings.get().unwrap();
+
+        let labels = TIMELAPSE_INTERVALS
+            .iter()
+            .map(|interval| interval_label(*interval))
+            .collect::<Vec<_>>();
+        let current = settings.uint("timelapse-interval");
+        let selected = TIMELAPSE_INTERVALS
+            .iter()
+            .position(|interval| *interval == current)
+            .unwrap_or(2);
+
+        let model = gtk::StringList::new(&labels.iter().map(String::as_str).collect::<Vec<_>>());
+        imp.timelapse_interval_row.set_model(Some(&model));
+        imp.timelapse_interval_row.set_selected(selected as u32);
+
+        imp.timelapse_interval_row.connect_selected_notify(
+            glib::clone!(@weak settings => move |row| {
+                if let Some(interval) = TIMELAPSE_INTERVALS.get(row.selected() as usize) {
+                    if let Err(err) = settings.set_uint("timelapse-interval", *interval) {
+                        log::error!("Could not store the time-lapse interval: {err}");
+                    }
+                }
+            }),
+        );
+    }
+
+    fn set_camera(&self, camera: &aperture::Camera) {
+        let imp = self.imp();
+
+        let modes = camera.modes();
+        if modes.is_empty() {
+            return;
+        }
+        imp.camera.replace(Some(camera.clone()));
+
+        let mut resolutions = vec![None];
+        for mode in modes.iter() {
+            let resolution = Some((mode.width(), mode.height()));
+            if !resolutions.contains(&resolution) {
+                resolutions.push(resolution);
+            }
+        }
+        let labels = resolutions
+            .iter()
+            .map(|resolution| match resolution {
+                Some((width, height)) => format!("{width}×{height}"),
+                None => gettext("Automatic"),
+            })
+            .collect::<Vec<_>>();
+
+        let stored_mode = utils::stored_camera_mode(imp.settings.get().unwrap(), camera);
+        let selected = stored_mode
+            .as_ref()
+            .and_then(|mode| {
+                resolutions
+                    .iter()
+                    .position(|r| *r == Some((mode.width(), mode.height())))
+            })
+            .unwrap_or_default();
+        imp.resolutions.replace(resolutions);
+
+        imp.updating.set(true);
+        let model = gtk::StringList::new(&labels.iter().map(String::as_str).collect::<Vec<_>>());
+        imp.resolution_row.set_model(Some(&model));
+        imp.resolution_row.set_selected(selected as u32);
+        self.update_framerates(stored_mode.and_then(|mode| mode.max_framerate()));
+        imp.updating.set(false);
+
+        imp.camera_group.set_visible(true);
+    }
+
+    fn selected_resolution(&self) -> Option<(i32, i32)> {
+        let imp = self.imp();
+
+        imp.resolutions
+            .borrow()
+            .get(imp.resolution_row.selected() as usize)
+            .copied()
+            .flatten()
+    }
+
+    fn selected_framerate(&self) -> Option<gst::Fraction> {
+        let imp = self.imp();
+
+        imp.framerates
+            .borrow()
+            .get(imp.framerate_row.selected() as usize)
+            .copied()
+    }
+
+    // Offers the framerates of the selected resolution, selecting `framerate`
+    // if available.
+    fn update_framerates(&self, framerate: Option<gst::Fraction>) {
+        let imp = self.imp();
+        let Some(camera) = imp.camera.borrow().clone() else {
+            return;
+        };
+
+        let mut framerates = Vec::new();
+        if let Some((width, height)) = self.selected_resolution() {
+            for mode in camera
+                .modes()
+                .iter()
+                .filter(|mode| mode.width() == width && mode.height() == height)
+            {
+                framerates.extend_from_slice(mode.framerates());
+            }
+            framerates.sort_by(|a, b| b.cmp(a));
+            framerates.dedup();
+        }
+
+        let labels = if framerates.is_empty() {
+            vec![gettext("Automatic")]
+        } else {
+            framerates.iter().map(|f| framerate_label(*f)).collect()
+        };
+        let selected = framerate
+            .and_then(|framerate| framerates.iter().position(|f| *f == framerate))
+            .unwrap_or_default();
+
+        imp.framerate_row.set_sensitive(!framerates.is_empty());
+        imp.framerates.replace(framerates);
+
+        let was_updating = imp.updating.replace(true);
+        let model = gtk::StringList::new(&labels.iter().map(String::as_str).collect::<Vec<_>>());
+        imp.framerate_row.set_model(Some(&model));
+        imp.framerate_row.set_selected(selected as u32);
+        imp.updating.set(was_updating);
+    }
+
+    fn on_resolution_selected(&self) {
+        if self.imp().updating.get() {
+            return;
+        }
+
+        self.update_framerates(self.selected_framerate());
+        self.store_camera_mode();
+    }
+
+    fn store_camera_mode(&self) {
+        let imp = self.imp();
+        if imp.updating.get() {
+            return;
+        }
+        let Some(camera) = imp.camera.borrow().clone() else {
+            return;
+        };
+
+        let mode = self
+            .selected_resolution()
+            .zip(self.selected_framerate())
+            .map(|((width, height), framerate)| (width, height, framerate));
+        utils::store_camera_mode(imp.settings.get().unwrap(), &camera, mode);
+    }
+}
+
+fn interval_label(seconds: u32) -> String {
+    // TRANSLATORS Do NOT translate {seconds}. This is the time between the
+    // frames of time-lapse videos, e.g. "5 seconds".
+    let label = ngettext("{seconds} second", "{seconds} seconds", seconds);
+    freplace(label, &[("seconds", &seconds.to_string())])
+}
+
+fn framerate_label(framerate: gst::Fraction) -> String {
+    let rate = if framerate.denom() == 1 {
+        framerate.numer().to_string()
+    } else {
+        format!("{:.2}", framerate.numer() as f64 / framerate.denom() as f64)
+    };
+
+    // TRANSLATORS Do NOT translate {framerate}. This will appear as, e.g. "30
+    // fps".
+    i18n_f("{framerate} fps", &[("framerate", &rate)])
+}
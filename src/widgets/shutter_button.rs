@@ -33,6 +33,10 @@ mod imp {
         pub shutter_mode: Cell<ShutterMode>,
         #[property(get, set = Self::set_countdown, explicit_notify)]
         pub countdown: Cell<u32>,
+        /// The number of pictures left to take by the intervalometer, not
+        /// shown when 0.
+        #[property(get, set = Self::set_remaining_shots, explicit_notify)]
+        pub remaining_shots: Cell<u32>,
 
         pub countdown_ani: OnceCell<adw::TimedAnimation>,
         pub record_ani: OnceCell<adw::TimedAnimation>,
@@ -50,6 +54,23 @@ mod imp {
             }
         }
 
+        pub fn set_remaining_shots(&self, remaining_shots: u32) {
+            let widget = self.obj();
+            if remaining_shots == self.remaining_shots.replace(remaining_shots) {
+                return;
+            }
+
+            if remaining_shots > 0 {
+                widget.set_label(&remaining_shots.to_string());
+                widget.add_css_class("intervalometer");
+            } else {
+                widget.set_child(None::<&gtk::Widget>);
+                widget.remove_css_class("intervalometer");
+            }
+
+            widget.notify_remaining_shots();
+        }
+
         pub fn set_shutter_mode(&self, shutter_mode: ShutterMode) {
             let widget = self.obj();
             if shutter_mode != self.shutter_mode.replace(shutter_mode) {
@@ -89,6 +110,19 @@ mod imp {
                         record_ani.set_value_to(1.0);
                         record_ani.play();
 
+                        widget.set_tooltip_text(Some(&gettext("Stop Recording")));
+                    }
+                    // The button keeps its recording shape but loses its
+                    // color.
+                    ShutterMode::RecordingPaused => {
+                        mode_ani.set_value_to(0.0);
+                        mode_ani.set_value_from(mode_from);
+                        mode_ani.play();
+
+                        record_ani.set_value_from(record_from);
+                        record_ani.set_value_to(1.0);
+                        record_ani.play();
+
                         widget.set_tooltip_text(Some(&gettext("Stop Recording")));
                     }
                 }
@@ -157,6 +191,20 @@ mod imp {
                 }));
             let record_ani = adw::TimedAnimation::new(&*widget, 0.0, 0.0, 250, record_target);
             self.record_ani.set(record_ani).unwrap();
+
+            // A long press takes a burst of pictures.
+            let long_press = gtk::GestureLongPress::new();
+            long_press.connect_pressed(glib::clone!(@weak widget => move |gesture, _, _| {
+                if !matches!(widget.shutter_mode(), ShutterMode::Picture) {
+                    return;
+                }
+
+                gesture.set_state(gtk::EventSequenceState::Claimed);
+                if let Err(err) = widget.activate_action("win.take-burst", None) {
+                    log::error!("Could not take burst: {err}");
+                }
+            }));
+            widget.add_controller(long_press);
         }
     }
 
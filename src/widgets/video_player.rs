@@ -55,9 +55,9 @@ mod imp {
                 glib::clone!(@weak widget => move |media_file| {
                     let window = widget.root().and_downcast::<crate::Window>().unwrap();
                     if media_file.is_playing() {
-                        window.inhibit("Playing Video");
+                        window.inhibit(crate::InhibitReason::Playback);
                     } else {
-                        window.uninhibit();
+                        window.uninhibit(crate::InhibitReason::Playback);
                     }
                 }),
             );
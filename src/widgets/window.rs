@@ -11,6 +11,7 @@ use crate::CaptureMode;
 
 mod imp {
     use std::cell::{Cell, RefCell};
+    use std::collections::HashMap;
 
     use adw::subclass::prelude::*;
     use gtk::CompositeTemplate;
@@ -35,9 +36,13 @@ mod imp {
 
         pub settings: gio::Settings,
         pub countdown_timer_id: RefCell<Option<glib::SourceId>>,
+        pub intervalometer_source: RefCell<Option<glib::SourceId>>,
+        // The pictures left to take by the intervalometer, `None` when taking
+        // pictures until stopped.
+        pub remaining_shots: Cell<Option<u32>>,
         pub is_active_handle: RefCell<Option<glib::SignalHandlerId>>,
 
-        pub inhibit_cookie: Cell<Option<u32>>,
+        pub inhibit_cookies: RefCell<HashMap<crate::InhibitReason, u32>>,
     }
 
     impl Default for Window {
@@ -52,9 +57,11 @@ mod imp {
 
                 settings: gio::Settings::new(APP_ID),
                 countdown_timer_id: Default::default(),
+                intervalometer_source: Default::default(),
+                remaining_shots: Default::default(),
                 is_active_handle: Default::default(),
 
-                inhibit_cookie: Default::default(),
+                inhibit_cookies: Default::default(),
             }
         }
     }
@@ -68,9 +75,12 @@ mod imp {
             if self.camera.is_recording_active() {
                 self.camera.stop_recording();
             }
+            obj.intervalometer_stop();
 
             match obj.capture_mode() {
-                CaptureMode::Video => obj.set_shutter_mode(crate::ShutterMode::Video),
+                CaptureMode::Video | CaptureMode::Loop | CaptureMode::Timelapse => {
+                    obj.set_shutter_mode(crate::ShutterMode::Video)
+                }
                 CaptureMode::Picture => obj.set_shutter_mode(crate::ShutterMode::Picture),
             }
 
@@ -101,13 +111,39 @@ mod imp {
                             log::error!("Could not take picture: {err}");
                             window.send_toast(&gettext("Could not take picture"));
                         }
-                        CaptureMode::Video => {
+                        CaptureMode::Video | CaptureMode::Loop | CaptureMode::Timelapse => {
                             log::error!("Could not record video: {err}");
                             window.send_toast(&gettext("Could not record video"));
                         }
                     }
                 };
             });
+            klass.install_action("win.take-burst", None, move |window, _, _| {
+                if let Err(err) = window.imp().camera.take_burst() {
+                    log::error!("Could not take burst: {err}");
+                    window.send_toast(&gettext("Could not take burst"));
+                }
+            });
+            klass.install_action_async("win.take-still", None, |window, _, _| async move {
+                if let Err(err) = window.imp().camera.take_picture().await {
+                    log::error!("Could not take picture: {err}");
+                    window.send_toast(&gettext("Could not take picture"));
+                }
+            });
+            klass.install_action("win.pause-recording", None, move |window, _, _| {
+                window.imp().camera.toggle_pause_recording();
+            });
+            klass.install_action(
+                "win.save-loop-recording",
+                None,
+                move |window, _, _| match window.imp().camera.save_loop_recording() {
+                    Ok(()) => window.send_toast(&gettext("Recent footage saved")),
+                    Err(err) => {
+                        log::error!("Could not save the loop recording: {err}");
+                        window.send_toast(&gettext("Could not save recent footage"));
+                    }
+                },
+            );
             klass.install_action("win.about", None, move |window, _, _| {
                 window.show_about_dialog();
             });
@@ -236,6 +272,15 @@ impl Window {
         );
         self.add_action(&countdown_action);
 
+        let intervalometer_action = self.imp().settings.create_action("intervalometer-interval");
+        self.imp().settings.connect_changed(
+            Some("intervalometer-interval"),
+            glib::clone!(@weak self as window => move |_, _| {
+                window.intervalometer_stop();
+            }),
+        );
+        self.add_action(&intervalometer_action);
+
         let capture_mode_action = self.imp().settings.create_action("capture-mode");
         self.imp().settings.connect_changed(
             Some("capture-mode"),
@@ -247,6 +292,14 @@ impl Window {
             }),
         );
         self.add_action(&capture_mode_action);
+
+        let effect_action = self.imp().settings.create_action("effect");
+        self.add_action(&effect_action);
+
+        let microphone_action = self.imp().settings.create_action("microphone");
+        self.add_action(&microphone_action);
+        let record_audio_action = self.imp().settings.create_action("record-audio");
+        self.add_action(&record_audio_action);
     }
 
     fn save_window_size(&self) -> Result<(), glib::BoolError> {
@@ -320,6 +373,12 @@ impl Window {
             duration,
             glib::clone!(@weak self as window => move || {
                 window.imp().countdown_timer_id.take();
+                // The countdown delays the first picture of the intervalometer.
+                if window.is_intervalometer_enabled() {
+                    window.countdown_cleanup();
+                    window.intervalometer_start();
+                    return;
+                }
                 glib::spawn_future_local(glib::clone!(@weak window => async move {
                     if let Err(err) = window.shutter_action().await {
                         match window.capture_mode() {
@@ -327,7 +386,7 @@ impl Window {
                                 log::error!("Could not take picture: {err}");
                                 window.send_toast(&gettext("Could not take picture"));
                             }
-                            CaptureMode::Video => {
+                            CaptureMode::Video | CaptureMode::Loop | CaptureMode::Timelapse => {
                                 log::error!("Could not record video: {err}");
                                 window.send_toast(&gettext("Could not record video"));
                             }
@@ -349,7 +408,11 @@ impl Window {
     async fn shutter_action(&self) -> anyhow::Result<()> {
         let imp = self.imp();
 
-        if matches!(self.capture_mode(), CaptureMode::Video) {
+        let capture_mode = self.capture_mode();
+        if matches!(
+            capture_mode,
+            CaptureMode::Video | CaptureMode::Loop | CaptureMode::Timelapse
+        ) {
             if imp.camera.is_recording_active() {
                 // disable the button while the video is ending
                 //
@@ -358,18 +421,97 @@ impl Window {
                 self.set_shutter_enabled(false);
                 imp.camera.stop_recording();
                 self.set_shutter_enabled(true);
+            } else if matches!(capture_mode, CaptureMode::Loop) {
+                imp.camera.start_loop_recording().await?;
             } else {
-                let format = imp.settings.enum_("video-format").into();
-                imp.camera.start_recording(format).await?;
+                imp.camera.start_recording().await?;
             }
         } else {
-            let format = imp.settings.enum_("picture-format").into();
-            imp.camera.take_picture(format).await?;
+            imp.camera.take_picture().await?;
         }
 
         Ok(())
     }
 
+    fn intervalometer_start(&self) {
+        let imp = self.imp();
+
+        let interval = imp.settings.uint("intervalometer-interval");
+        let shots = imp.settings.uint("intervalometer-shots");
+        imp.remaining_shots.set((shots > 0).then_some(shots));
+        self.inhibit(crate::InhibitReason::Intervalometer);
+
+        let source = glib::timeout_add_seconds_local(
+            interval,
+            glib::clone!(@weak self as window => @default-return glib::ControlFlow::Break, move || {
+                window.intervalometer_shoot();
+                glib::ControlFlow::Continue
+            }),
+        );
+        if let Some(old_source) = imp.intervalometer_source.replace(Some(source)) {
+            old_source.remove();
+        }
+
+        // The first picture is taken right away.
+        self.intervalometer_shoot();
+    }
+
+    fn intervalometer_shoot(&self) {
+        glib::spawn_future_local(glib::clone!(@weak self as window => async move {
+            match window.imp().camera.take_picture().await {
+                Ok(()) => window.intervalometer_count_shot(),
+                // Pictures that could not be taken, e.g. because the previous
+                // one is still being saved, are not counted.
+                Err(err) => {
+                    log::error!("Could not take picture: {err}");
+                    window.send_toast(&gettext("Could not take picture"));
+                }
+            }
+        }));
+    }
+
+    fn intervalometer_count_shot(&self) {
+        let imp = self.imp();
+
+        // The intervalometer might have been stopped in the meantime.
+        let Some(remaining) = imp.remaining_shots.get() else {
+            return;
+        };
+        let remaining = remaining.saturating_sub(1);
+        imp.remaining_shots.set(Some(remaining));
+        imp.camera.set_remaining_shots(remaining);
+
+        if remaining == 0 {
+            self.intervalometer_stop();
+        }
+    }
+
+    fn intervalometer_stop(&self) {
+        if let Some(source) = self.imp().intervalometer_source.take() {
+            source.remove();
+            self.intervalometer_cleanup();
+        }
+    }
+
+    fn intervalometer_cleanup(&self) {
+        let imp = self.imp();
+
+        imp.remaining_shots.set(None);
+        imp.camera.set_remaining_shots(0);
+        self.uninhibit(crate::InhibitReason::Intervalometer);
+    }
+
+    fn is_intervalometer_active(&self) -> bool {
+        self.imp().intervalometer_source.borrow().is_some()
+    }
+
+    /// Whether pictures are taken with the intervalometer rather than one at
+    /// a time.
+    fn is_intervalometer_enabled(&self) -> bool {
+        matches!(self.capture_mode(), CaptureMode::Picture)
+            && self.imp().settings.uint("intervalometer-interval") > 0
+    }
+
     fn countdown(&self) -> i32 {
         self.imp().settings.int("countdown")
     }
@@ -382,12 +524,18 @@ impl Window {
         let imp = self.imp();
         if imp.camera.is_recording_active() {
             self.shutter_action().await?;
+        } else if self.is_intervalometer_active() {
+            self.intervalometer_stop();
         } else if self.countdown() > 0 {
+            // When the intervalometer is enabled, it starts once the countdown
+            // ends.
             if self.is_countdown_active() {
                 self.countdown_cancel();
             } else {
                 self.countdown_start();
             }
+        } else if self.is_intervalometer_enabled() {
+            self.intervalometer_start();
         } else {
             self.shutter_action().await?;
         }
@@ -400,12 +548,13 @@ impl Window {
 
     fn set_capture_mode(&self, capture_mode: CaptureMode) {
         self.countdown_cancel();
+        self.intervalometer_stop();
 
         match capture_mode {
             CaptureMode::Picture => {
                 self.set_shutter_mode(crate::ShutterMode::Picture);
             }
-            CaptureMode::Video => {
+            CaptureMode::Video | CaptureMode::Loop | CaptureMode::Timelapse => {
                 self.set_shutter_mode(crate::ShutterMode::Video);
             }
         }
@@ -416,7 +565,7 @@ impl Window {
     }
 
     fn show_preferences_window(&self) {
-        let preferences = crate::PreferencesWindow::default();
+        let preferences = crate::PreferencesWindow::new(self.imp().camera.camera().as_ref());
         preferences.present(self);
     }
 
@@ -429,28 +578,38 @@ impl Window {
         self.action_set_enabled("win.take-picture", enabled);
     }
 
-    pub fn inhibit(&self, reason: &str) {
+    /// Keeps the session from going idle until [`Self::uninhibit`] is called
+    /// with the same `reason`.
+    pub fn inhibit(&self, reason: crate::InhibitReason) {
         let imp = self.imp();
         let Some(app) = self.application() else {
             return;
         };
 
         // We make sure the app is not doubly inhibited.
-        self.uninhibit();
+        if imp.inhibit_cookies.borrow().contains_key(&reason) {
+            return;
+        }
 
-        let cookie = app.inhibit(Some(self), gtk::ApplicationInhibitFlags::IDLE, Some(reason));
+        let reason_string = reason.translatable_string();
+        let cookie = app.inhibit(
+            Some(self),
+            gtk::ApplicationInhibitFlags::IDLE,
+            Some(&reason_string),
+        );
         if cookie > 0 {
-            log::debug!("Inhibiting app with reason '{reason}' and cookie: {cookie}");
-            imp.inhibit_cookie.set(Some(cookie));
+            log::debug!("Inhibiting app with reason '{reason_string}' and cookie: {cookie}");
+            imp.inhibit_cookies.borrow_mut().insert(reason, cookie);
         }
     }
 
-    pub fn uninhibit(&self) {
+    pub fn uninhibit(&self, reason: crate::InhibitReason) {
         let imp = self.imp();
         let Some(app) = self.application() else {
             return;
         };
-        if let Some(cookie) = imp.inhibit_cookie.take() {
+        let cookie = imp.inhibit_cookies.borrow_mut().remove(&reason);
+        if let Some(cookie) = cookie {
             log::debug!("Uninhibiting app with cookie: {cookie}");
             app.uninhibit(cookie);
         }